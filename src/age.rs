@@ -0,0 +1,108 @@
+use std::time::Duration;
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 60 * MINUTE;
+const DAY: u64 = 24 * HOUR;
+const YEAR: u64 = 365 * DAY;
+
+/// Parses the age printed by kubectl (ex: `45s`, `5m30s`, `2d3h`, `3y`), `None` when it can't
+/// be parsed or doesn't fit.
+pub fn parse_age(age: &str) -> Option<Duration> {
+    let mut seconds: u64 = 0;
+    let mut number: Option<u64> = None;
+
+    for c in age.chars() {
+        match c.to_digit(10) {
+            Some(digit) => {
+                let digits = number.unwrap_or(0).checked_mul(10)?;
+                number = Some(digits.checked_add(u64::from(digit))?);
+            }
+            None => {
+                let unit = match c {
+                    's' => 1,
+                    'm' => MINUTE,
+                    'h' => HOUR,
+                    'd' => DAY,
+                    'y' => YEAR,
+                    _ => return None,
+                };
+                seconds = seconds.checked_add(number.take()?.checked_mul(unit)?)?;
+            }
+        }
+    }
+
+    match number {
+        Some(_) => None,
+        None if age.is_empty() => None,
+        None => Some(Duration::from_secs(seconds)),
+    }
+}
+
+/// Formats the age the way kubectl does, more precise for the younger ones.
+pub fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    match seconds {
+        s if s < 2 * MINUTE => format!("{s}s"),
+        s if s < 10 * MINUTE => with_remainder(s, MINUTE, "m", 1, "s"),
+        s if s < 3 * HOUR => format!("{}m", s / MINUTE),
+        s if s < 8 * HOUR => with_remainder(s, HOUR, "h", MINUTE, "m"),
+        s if s < 2 * DAY => format!("{}h", s / HOUR),
+        s if s < 8 * DAY => with_remainder(s, DAY, "d", HOUR, "h"),
+        s if s < 2 * YEAR => format!("{}d", s / DAY),
+        s if s < 8 * YEAR => with_remainder(s, YEAR, "y", DAY, "d"),
+        s => format!("{}y", s / YEAR),
+    }
+}
+
+/// Leaves out the smaller unit when it is zero, ex: `3h` instead of `3h0m`.
+fn with_remainder(
+    seconds: u64,
+    unit: u64,
+    suffix: &str,
+    small_unit: u64,
+    small_suffix: &str,
+) -> String {
+    match (seconds % unit) / small_unit {
+        0 => format!("{}{suffix}", seconds / unit),
+        remainder => format!("{}{suffix}{remainder}{small_suffix}", seconds / unit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_age, parse_age};
+
+    #[test]
+    fn parse_all_kubectl_formats() {
+        assert_eq!(parse_age("45s"), Some(Duration::from_secs(45)));
+        assert_eq!(parse_age("5m30s"), Some(Duration::from_secs(330)));
+        assert_eq!(parse_age("2d3h"), Some(Duration::from_secs(51 * 3600)));
+        assert_eq!(parse_age("3y"), Some(Duration::from_secs(3 * 365 * 86400)));
+        assert_eq!(parse_age("2y10d"), Some(Duration::from_secs(740 * 86400)));
+    }
+
+    #[test]
+    fn reject_invalid_ages() {
+        assert_eq!(parse_age(""), None);
+        assert_eq!(parse_age("18"), None);
+        assert_eq!(parse_age("<invalid>"), None);
+    }
+
+    #[test]
+    fn reject_ages_which_overflow() {
+        assert_eq!(parse_age("99999999999999999999999s"), None);
+        assert_eq!(parse_age("99999999999999999y"), None);
+        assert_eq!(parse_age("18446744073709551615s1s"), None);
+    }
+
+    #[test]
+    fn format_like_kubectl() {
+        assert_eq!(format_age(Duration::from_secs(45)), "45s");
+        assert_eq!(format_age(Duration::from_secs(330)), "5m30s");
+        assert_eq!(format_age(Duration::from_secs(3 * 3600)), "3h");
+        assert_eq!(format_age(Duration::from_secs(51 * 3600)), "2d3h");
+        assert_eq!(format_age(Duration::from_secs(30 * 86400)), "30d");
+    }
+}
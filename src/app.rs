@@ -1,29 +1,119 @@
-use crate::{input::InputAction, load_all_pods, load_logs, pod::Pod, ui::InputContext};
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::Path,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
+use crate::{
+    apply_manifest,
+    cluster_event::ClusterEvent,
+    deployment::Deployment,
+    diff_manifest,
+    errors::Error,
+    fuzzy::{fuzzy_match, FuzzyMatch},
+    input::InputAction,
+    load_all_pods, load_contexts, load_deployments, load_events, load_logs, load_manifest,
+    load_namespaces, load_nodes, load_pod_metrics, load_resource_kinds, load_resources,
+    manifest::Manifest,
+    node::Node,
+    pod::{Pod, PodColumn},
+    resource_table::ResourceTable,
+    ui::{InputContext, View},
+};
+
+pub use self::command_output::CommandOutput;
+use self::job::JobOutput;
+pub use self::job::{Job, JobKind, JobOutcome, JobRequest, JobResult, KubectlCommand};
 use self::logs_keeper::LogsKeeper;
+pub use self::metrics_keeper::MetricsKeeper;
 
+mod command_output;
+mod job;
 mod logs_keeper;
+mod metrics_keeper;
+
+/// Tells the apps of the workspaces apart, so the job results go back to the one which
+/// started them.
+static NEXT_APP_ID: AtomicUsize = AtomicUsize::new(0);
+
 pub struct App {
+    id: usize,
     running: bool,
     namespace: String,
-    pods: Option<Vec<Pod>>,
+    /// kubectl context, the current one when there is none.
+    context: Option<String>,
+    pod_selector: Option<String>,
+    pod_field_selector: Option<String>,
+    pods: Vec<Pod>,
+    pod_filter: Option<String>,
+    filtered_pods: Vec<(usize, FuzzyMatch)>,
+    pod_sort: (PodColumn, bool),
     pod_logs: LogsKeeper,
+    pod_metrics: MetricsKeeper,
+    manifest: Option<Manifest>,
+    deployments: Vec<Deployment>,
+    command_output: Option<CommandOutput>,
+    /// Kinds, namespaces and contexts are loaded once, as they rarely change while the app is
+    /// running.
+    resource_kinds: Vec<String>,
+    namespaces: Vec<String>,
+    contexts: Vec<String>,
+    resources: Option<ResourceTable>,
+    events: Vec<ClusterEvent>,
+    events_pod_name: Option<String>,
+    nodes: Vec<Node>,
+    /// Number of the latest job of each kind still running.
+    loading: HashMap<JobKind, u64>,
+    /// Kinds and numbers of the running jobs which keep every result, ex: commands.
+    running_commands: HashSet<(JobKind, u64)>,
+    jobs_started: u64,
 }
 
 impl App {
-    pub fn new(namespace: String) -> App {
+    pub fn new(
+        namespace: String,
+        context: Option<String>,
+        pod_selector: Option<String>,
+        pod_field_selector: Option<String>,
+    ) -> App {
         let running = true;
-        let pods = Some(load_all_pods(&namespace).expect("couldn't load initial pods"));
         let pod_logs = LogsKeeper::default();
+        let manifest = None;
 
         App {
+            id: NEXT_APP_ID.fetch_add(1, Ordering::Relaxed),
             running,
             namespace,
-            pods,
+            context,
+            pod_selector,
+            pod_field_selector,
+            pods: vec![],
+            pod_filter: None,
+            filtered_pods: vec![],
+            pod_sort: (PodColumn::Name, false),
             pod_logs,
+            pod_metrics: MetricsKeeper::default(),
+            manifest,
+            deployments: vec![],
+            command_output: None,
+            resource_kinds: vec![],
+            namespaces: vec![],
+            contexts: vec![],
+            resources: None,
+            events: vec![],
+            events_pod_name: None,
+            nodes: vec![],
+            loading: HashMap::new(),
+            running_commands: HashSet::new(),
+            jobs_started: 0,
         }
     }
 
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
     fn exit(&mut self) {
         self.running = false;
     }
@@ -36,19 +126,166 @@ impl App {
         &self.namespace
     }
 
-    pub fn pods(&self) -> Option<&Vec<Pod>> {
-        self.pods.as_ref()
+    pub fn context(&self) -> Option<&str> {
+        self.context.as_deref()
+    }
+
+    /// Pods matching the selectors, regardless of the filter.
+    pub fn all_pods(&self) -> &[Pod] {
+        &self.pods
+    }
+
+    /// Pods passing the filter, with the characters of their names matched by it.
+    pub fn pods(&self) -> Vec<(&Pod, &FuzzyMatch)> {
+        self.filtered_pods
+            .iter()
+            .map(|(index, matched)| (&self.pods[*index], matched))
+            .collect()
+    }
+
+    /// Index is relative to the filtered pods.
+    pub fn get_pod(&self, index: usize) -> Option<&Pod> {
+        let (index, _) = self.filtered_pods.get(index)?;
+        self.pods.get(*index)
+    }
+
+    pub fn find_pod(&self, name: &str) -> Option<usize> {
+        (0..self.get_pods_number()).find(|index| self.get_pod_name(*index) == Some(name))
+    }
+
+    pub fn pod_filter(&self) -> Option<&str> {
+        self.pod_filter.as_deref()
+    }
+
+    pub fn set_pod_filter(&mut self, filter: Option<String>) {
+        self.pod_filter = filter.filter(|it| !it.is_empty());
+        self.filter_pods();
     }
 
-    pub fn get_pod(&self, index: usize) -> &Pod {
-        &self
+    /// Column the pods are sorted by, and whether the order is descending.
+    pub fn pod_sort(&self) -> (PodColumn, bool) {
+        self.pod_sort
+    }
+
+    pub fn sort_pods(&mut self, column: PodColumn, descending: bool) {
+        self.pod_sort = (column, descending);
+        self.filter_pods();
+    }
+
+    /// Pods are matched by name, or by status when the name doesn't match.
+    fn filter_pods(&mut self) {
+        let query = self.pod_filter.as_deref().unwrap_or_default();
+        self.filtered_pods = self
             .pods
-            .as_ref()
-            .expect("pods are loaded before the app right now")[index]
+            .iter()
+            .enumerate()
+            .filter_map(|(index, pod)| {
+                fuzzy_match(query, &pod.name)
+                    .or_else(|| fuzzy_match(query, &pod.status).map(|_| FuzzyMatch::default()))
+                    .map(|matched| (index, matched))
+            })
+            .collect();
+
+        let pods = &self.pods;
+        let (column, descending) = self.pod_sort;
+        self.filtered_pods.sort_by(|(a, _), (b, _)| {
+            let ordering = column.compare(&pods[*a], &pods[*b]);
+            match descending {
+                true => ordering.reverse(),
+                false => ordering,
+            }
+        });
+    }
+
+    pub fn get_all_pods_number(&self) -> usize {
+        self.pods.len()
+    }
+
+    pub fn get_pod_name(&self, index: usize) -> Option<&str> {
+        self.get_pod(index).map(|it| it.name.as_str())
+    }
+
+    /// Label selector narrowing the pods list, set when drilling into a deployment or by the user.
+    pub fn pod_selector(&self) -> Option<&str> {
+        self.pod_selector.as_deref()
+    }
+
+    /// Field selector narrowing the pods list, set when drilling into a node or by the user.
+    pub fn pod_field_selector(&self) -> Option<&str> {
+        self.pod_field_selector.as_deref()
+    }
+
+    pub fn pod_metrics(&self) -> &MetricsKeeper {
+        &self.pod_metrics
+    }
+
+    pub fn deployments(&self) -> &Vec<Deployment> {
+        &self.deployments
+    }
+
+    pub fn get_deployment(&self, index: usize) -> Option<&Deployment> {
+        self.deployments.get(index)
+    }
+
+    pub fn get_deployments_number(&self) -> usize {
+        self.deployments.len()
+    }
+
+    pub fn command_output(&self) -> Option<&CommandOutput> {
+        self.command_output.as_ref()
+    }
+
+    pub fn clear_command_output(&mut self) {
+        self.command_output = None;
+    }
+
+    pub fn resource_kinds(&self) -> &Vec<String> {
+        &self.resource_kinds
+    }
+
+    pub fn namespaces(&self) -> &Vec<String> {
+        &self.namespaces
+    }
+
+    pub fn contexts(&self) -> &Vec<String> {
+        &self.contexts
+    }
+
+    pub fn resources(&self) -> Option<&ResourceTable> {
+        self.resources.as_ref()
     }
 
-    pub fn get_pod_name(&self, index: usize) -> &str {
-        &self.get_pod(index).name
+    pub fn get_resources_number(&self) -> usize {
+        self.resources.as_ref().map(|it| it.len()).unwrap_or(0)
+    }
+
+    pub fn events(&self) -> &Vec<ClusterEvent> {
+        &self.events
+    }
+
+    pub fn get_events_number(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Pod whose events are shown, `None` when showing events of the whole namespace.
+    pub fn events_pod_name(&self) -> Option<&str> {
+        self.events_pod_name.as_deref()
+    }
+
+    pub fn set_events_pod_name(&mut self, pod_name: Option<String>) {
+        self.events_pod_name = pod_name;
+    }
+
+    pub fn nodes(&self) -> &Vec<Node> {
+        &self.nodes
+    }
+
+    pub fn get_node(&self, index: usize) -> Option<&Node> {
+        self.nodes.get(index)
+    }
+
+    pub fn get_nodes_number(&self) -> usize {
+        self.nodes.len()
     }
 
     pub fn pod_logs(&self) -> Option<&Vec<String>> {
@@ -59,25 +296,538 @@ impl App {
         self.pod_logs.pod_name().map(|it| it.to_owned())
     }
 
+    pub fn manifest(&self) -> Option<&Manifest> {
+        self.manifest.as_ref()
+    }
+
     pub fn get_pods_number(&self) -> usize {
-        self.pods.as_ref().map(|it| it.len()).unwrap_or(0)
+        self.filtered_pods.len()
     }
 
-    pub fn take_action(&mut self, action: InputAction, context: InputContext) {
+    pub fn is_loading(&self, kind: JobKind) -> bool {
+        self.loading.contains_key(&kind) || self.running_commands.iter().any(|it| it.0 == kind)
+    }
+
+    /// Marks the job as loading and prepares it for a worker thread, with copies of what it
+    /// needs from the app.
+    pub fn start_job(&mut self, request: JobRequest) -> Job {
+        let kind = request.kind();
+        self.jobs_started += 1;
+        if kind.keeps_every_result() {
+            self.running_commands.insert((kind, self.jobs_started));
+        } else {
+            self.loading.insert(kind, self.jobs_started);
+        }
+        if kind == JobKind::Manifest {
+            self.manifest = None;
+        }
+
+        let namespace = self.namespace.clone();
+        let context = self.context.clone();
+        let selector = self.pod_selector.clone();
+        let field_selector = self.pod_field_selector.clone();
+        let events_pod_name = self.events_pod_name.clone();
+        let manifest = self.manifest.clone();
+        Job::new(self.id, kind, self.jobs_started, move || {
+            let context = context.as_deref();
+            match request {
+                JobRequest::Pods => JobOutput::Pods(load_all_pods(
+                    &namespace,
+                    selector.as_deref(),
+                    field_selector.as_deref(),
+                    context,
+                )),
+                JobRequest::SelectPods {
+                    selector,
+                    field_selector,
+                } => {
+                    let pods = load_all_pods(
+                        &namespace,
+                        selector.as_deref(),
+                        field_selector.as_deref(),
+                        context,
+                    );
+                    JobOutput::SelectedPods {
+                        selector,
+                        field_selector,
+                        pods,
+                    }
+                }
+                JobRequest::Logs { pod_name, since } => {
+                    let logs = load_logs(&pod_name, &namespace, since.as_deref(), context);
+                    JobOutput::Logs(pod_name, logs)
+                }
+                JobRequest::Metrics => JobOutput::Metrics(load_pod_metrics(&namespace, context)),
+                JobRequest::Events => {
+                    JobOutput::Events(load_events(&namespace, events_pod_name.as_deref(), context))
+                }
+                JobRequest::Deployments => {
+                    JobOutput::Deployments(load_deployments(&namespace, context))
+                }
+                JobRequest::Nodes => JobOutput::Nodes(load_nodes(context)),
+                JobRequest::Resources(kind) => {
+                    JobOutput::Resources(load_resources(&kind, &namespace, context))
+                }
+                JobRequest::Manifest { kind, name } => {
+                    JobOutput::Manifest(fetch_manifest(&kind, &name, &namespace, context))
+                }
+                JobRequest::Command(command) => {
+                    let output = command.run(&namespace, context);
+                    JobOutput::Command(command, output)
+                }
+                JobRequest::ReviewChanges(path) => {
+                    let diff = review_changes(&path, manifest.as_ref(), context);
+                    JobOutput::Reviewed(path, diff)
+                }
+                JobRequest::ApplyChanges(path) => {
+                    let output = apply_changes(&path, manifest.as_ref(), &namespace, context);
+                    JobOutput::Applied(path, output)
+                }
+                JobRequest::ResourceKinds => JobOutput::ResourceKinds(load_resource_kinds(context)),
+                JobRequest::Namespaces => JobOutput::Namespaces(load_namespaces(context)),
+                JobRequest::Contexts => JobOutput::Contexts(load_contexts()),
+            }
+        })
+    }
+
+    /// Applies the result of a job, unless a newer job of the same kind has been started since
+    /// and the kind doesn't keep every result.
+    pub fn finish_job(&mut self, result: JobResult) -> Result<JobOutcome, Error> {
+        let kind = result.kind();
+        if kind.keeps_every_result() {
+            if !self.running_commands.remove(&(kind, result.number)) {
+                return Ok(JobOutcome::Done);
+            }
+        } else if self.loading.get(&kind) == Some(&result.number) {
+            self.loading.remove(&kind);
+        } else {
+            return Ok(JobOutcome::Done);
+        }
+
+        match result.output {
+            JobOutput::Pods(pods) => {
+                self.pods = pods?;
+                self.filter_pods();
+            }
+            JobOutput::SelectedPods {
+                selector,
+                field_selector,
+                pods,
+            } => {
+                // Selectors are kept only when kubectl accepts them, so a typo doesn't empty the list
+                self.pods = pods?;
+                self.pod_selector = selector;
+                self.pod_field_selector = field_selector;
+                self.filter_pods();
+            }
+            JobOutput::Logs(pod_name, logs) => self.pod_logs = LogsKeeper::new(pod_name, logs?),
+            JobOutput::Metrics(metrics) => self.pod_metrics.update(metrics?),
+            JobOutput::Events(events) => self.events = events?,
+            JobOutput::Deployments(deployments) => self.deployments = deployments?,
+            JobOutput::Nodes(nodes) => self.nodes = nodes?,
+            JobOutput::Resources(resources) => self.resources = Some(resources?),
+            JobOutput::Manifest(manifest) => self.manifest = Some(manifest?),
+            JobOutput::Command(command, output) => {
+                self.command_output = Some(CommandOutput::new(command.title(), &output?));
+                let listed_kind = self.resources.as_ref().map(|it| it.kind());
+                if let Some(reload) = command.reload(listed_kind) {
+                    return Ok(JobOutcome::Then(reload));
+                }
+            }
+            JobOutput::ResourceKinds(kinds) => self.resource_kinds = kinds?,
+            JobOutput::Namespaces(namespaces) => self.namespaces = namespaces?,
+            JobOutput::Contexts(contexts) => self.contexts = contexts?,
+            JobOutput::Panicked(message) => return Err(Error::JobPanicked(message)),
+            JobOutput::Reviewed(path, diff) => return Ok(JobOutcome::Reviewed(path, diff)),
+            JobOutput::Applied(path, output) => {
+                let output = output.map(|(output, manifest)| {
+                    self.manifest = manifest;
+                    output
+                });
+                return Ok(JobOutcome::Applied(path, output));
+            }
+        }
+        Ok(JobOutcome::Done)
+    }
+
+    /// Returns the job to run in the background for the action, if it needs one.
+    pub fn take_action(
+        &mut self,
+        action: InputAction,
+        context: InputContext,
+    ) -> Option<JobRequest> {
         match action {
             InputAction::Quit => {
                 self.exit();
             }
             InputAction::FetchLogs => {
-                let pod_name = self.get_pod_name(context.selected_pod_index);
-                self.pod_logs =
-                    LogsKeeper::new(pod_name.to_owned(), fetch_logs(pod_name, &self.namespace))
+                let pod_name = self.get_pod_name(context.selected_pod_index)?;
+                return Some(JobRequest::Logs {
+                    pod_name: pod_name.to_owned(),
+                    since: None,
+                });
+            }
+            InputAction::ShowManifest => {
+                let (kind, name) = match context.view {
+                    View::Deployments => self
+                        .get_deployment(context.selected_deployment_index)
+                        .map(|it| ("deployment", it.name.as_str())),
+                    View::Nodes => self
+                        .get_node(context.selected_node_index)
+                        .map(|it| ("node", it.name.as_str())),
+                    View::Resources => self.resources.as_ref().and_then(|resources| {
+                        resources
+                            .name(context.selected_resource_index)
+                            .map(|name| (resources.kind(), name))
+                    }),
+                    _ => self
+                        .get_pod_name(context.selected_pod_index)
+                        .map(|it| ("pod", it)),
+                }?;
+                return Some(JobRequest::Manifest {
+                    kind: kind.to_owned(),
+                    name: name.to_owned(),
+                });
+            }
+            InputAction::CloseManifest => {
+                self.manifest = None;
+            }
+            InputAction::ShowDeploymentPods => {
+                let deployment = self.get_deployment(context.selected_deployment_index)?;
+                self.pod_selector = Some(deployment.selector.clone());
+                self.pod_field_selector = None;
+                return Some(JobRequest::Pods);
+            }
+            InputAction::ShowNodePods => {
+                let node = self.get_node(context.selected_node_index)?;
+                self.pod_field_selector = Some(format!("spec.nodeName={}", node.name));
+                self.pod_selector = None;
+                return Some(JobRequest::Pods);
+            }
+            InputAction::ClearPodSelector => {
+                self.pod_selector = None;
+                self.pod_field_selector = None;
+                return Some(JobRequest::Pods);
+            }
+            InputAction::DescribeResource => {
+                let resources = self.resources.as_ref()?;
+                let name = resources.name(context.selected_resource_index)?;
+                return Some(JobRequest::Command(KubectlCommand::Describe {
+                    kind: resources.kind().to_owned(),
+                    name: name.to_owned(),
+                }));
             }
             _ => {}
         }
+        None
     }
 }
 
-fn fetch_logs(pod_name: &str, namespace: &str) -> Vec<String> {
-    load_logs(pod_name, namespace).expect("todo load logs")
+/// Validates the edited manifest with a server-side dry run and returns the diff against the
+/// live resource, `None` when nothing has changed.
+fn review_changes(
+    path: &Path,
+    manifest: Option<&Manifest>,
+    context: Option<&str>,
+) -> Result<Option<String>, Error> {
+    let edited = fs::read_to_string(path)?;
+    if manifest.map(|it| it.yaml()) == Some(edited) {
+        return Ok(None);
+    }
+
+    apply_manifest(path, true, context)?;
+    diff_manifest(path, context)
+}
+
+/// Returns the output of kubectl, with the manifest as it is once applied.
+fn apply_changes(
+    path: &Path,
+    manifest: Option<&Manifest>,
+    namespace: &str,
+    context: Option<&str>,
+) -> Result<(String, Option<Manifest>), Error> {
+    let output = apply_manifest(path, false, context)?;
+    let manifest = match manifest {
+        Some(manifest) => Some(fetch_manifest(
+            manifest.kind(),
+            manifest.name(),
+            namespace,
+            context,
+        )?),
+        None => None,
+    };
+    Ok((output, manifest))
+}
+
+fn fetch_manifest(
+    kind: &str,
+    name: &str,
+    namespace: &str,
+    context: Option<&str>,
+) -> Result<Manifest, Error> {
+    let yaml = load_manifest(kind, name, namespace, context)?;
+    Ok(Manifest::new(kind, name, &yaml))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn review_and_apply_the_edited_manifest() {
+        // given
+        let dir = tempfile::tempdir().unwrap();
+        let edited = dir.path().join("api.yaml");
+        let mut app = App::new("default".to_owned(), None, None, None);
+        app.manifest = Some(Manifest::new("deployment", "api", "replicas: 1\n"));
+
+        // when
+        fs::write(&edited, "replicas: 1\n").unwrap();
+        let job = app.start_job(JobRequest::ReviewChanges(edited.clone()));
+        let unchanged = app.finish_job(job.run()).unwrap();
+        fs::write(&edited, "replicas: 2\n").unwrap();
+        app.start_job(JobRequest::ReviewChanges(edited.clone()));
+        let path = edited.clone();
+        let reviewed = Job::new(app.id(), JobKind::Changes, 2, move || {
+            JobOutput::Reviewed(path, Ok(Some("+  replicas: 2\n".to_owned())))
+        });
+        let diff = app.finish_job(reviewed.run()).unwrap();
+        app.start_job(JobRequest::ApplyChanges(edited.clone()));
+        let path = edited.clone();
+        let applied = Job::new(app.id(), JobKind::Changes, 3, move || {
+            let manifest = Manifest::new("deployment", "api", "replicas: 2\n");
+            JobOutput::Applied(
+                path,
+                Ok((
+                    "deployment.apps/api configured\n".to_owned(),
+                    Some(manifest),
+                )),
+            )
+        });
+        let applied = app.finish_job(applied.run()).unwrap();
+
+        // then
+        assert!(matches!(unchanged, JobOutcome::Reviewed(_, Ok(None))));
+        assert!(matches!(
+            diff,
+            JobOutcome::Reviewed(_, Ok(Some(diff))) if diff == "+  replicas: 2\n"
+        ));
+        assert!(matches!(
+            applied,
+            JobOutcome::Applied(_, Ok(output)) if output == "deployment.apps/api configured\n"
+        ));
+        assert!(!app.is_loading(JobKind::Changes));
+        assert_eq!(
+            app.manifest().map(|it| it.yaml()).as_deref(),
+            Some("replicas: 2\n")
+        );
+    }
+
+    fn input_context(view: View) -> InputContext {
+        InputContext {
+            view,
+            selected_pod_index: 0,
+            selected_deployment_index: 0,
+            selected_resource_index: 0,
+            selected_node_index: 0,
+        }
+    }
+
+    #[test]
+    fn narrow_the_pods_to_the_deployment_or_the_node() {
+        // given
+        let mut app = App::new(
+            "default".to_owned(),
+            None,
+            Some("tier=backend".to_owned()),
+            Some("status.phase=Running".to_owned()),
+        );
+        app.deployments = vec![
+            "api   2/3     3            2           12d   api   registry/api:1.2   app=api"
+                .parse()
+                .unwrap(),
+        ];
+        app.nodes = vec!["node-1   Ready   worker   120d   v1.25.3".parse().unwrap()];
+
+        // when
+        let deployment_job = app.take_action(
+            InputAction::ShowDeploymentPods,
+            input_context(View::Deployments),
+        );
+        let deployment_selector = app.pod_selector().map(|it| it.to_owned());
+        let deployment_field_selector = app.pod_field_selector().map(|it| it.to_owned());
+        let node_job = app.take_action(InputAction::ShowNodePods, input_context(View::Nodes));
+
+        // then
+        assert!(matches!(deployment_job, Some(JobRequest::Pods)));
+        assert_eq!(deployment_selector.as_deref(), Some("app=api"));
+        assert_eq!(deployment_field_selector, None);
+        assert!(matches!(node_job, Some(JobRequest::Pods)));
+        assert_eq!(app.pod_selector(), None);
+        assert_eq!(app.pod_field_selector(), Some("spec.nodeName=node-1"));
+    }
+
+    #[test]
+    fn clear_the_pod_selectors() {
+        // given
+        let mut app = App::new(
+            "default".to_owned(),
+            None,
+            Some("app=api".to_owned()),
+            Some("status.phase=Running".to_owned()),
+        );
+
+        // when
+        let job = app.take_action(InputAction::ClearPodSelector, input_context(View::Pods));
+
+        // then
+        assert!(matches!(job, Some(JobRequest::Pods)));
+        assert_eq!(app.pod_selector(), None);
+        assert_eq!(app.pod_field_selector(), None);
+    }
+
+    #[test]
+    fn ignore_the_results_of_outdated_jobs() {
+        // given
+        let mut app = App::new("default".to_owned(), None, None, None);
+        app.start_job(JobRequest::Pods);
+        app.start_job(JobRequest::Pods);
+        let outdated = Job::new(app.id(), JobKind::Pods, 1, || JobOutput::Pods(Ok(vec![])));
+        let latest = Job::new(app.id(), JobKind::Pods, 2, || JobOutput::Pods(Ok(vec![])));
+
+        // when
+        app.finish_job(outdated.run()).unwrap();
+        let loading_after_outdated = app.is_loading(JobKind::Pods);
+        app.finish_job(latest.run()).unwrap();
+
+        // then
+        assert!(loading_after_outdated);
+        assert!(!app.is_loading(JobKind::Pods));
+    }
+
+    #[test]
+    fn fail_the_jobs_which_panic() {
+        // given
+        let mut app = App::new("default".to_owned(), None, None, None);
+        app.start_job(JobRequest::Deployments);
+        let job = Job::new(app.id(), JobKind::Deployments, 1, || {
+            panic!("unexpected kubectl output")
+        });
+
+        // when
+        let result = app.finish_job(job.run());
+
+        // then
+        assert!(matches!(
+            result,
+            Err(Error::JobPanicked(message)) if message == "unexpected kubectl output"
+        ));
+        assert!(!app.is_loading(JobKind::Deployments));
+    }
+
+    #[test]
+    fn select_pods_over_the_pods_loading() {
+        // given
+        let mut app = App::new("default".to_owned(), None, None, None);
+        app.start_job(JobRequest::Pods);
+        app.start_job(JobRequest::SelectPods {
+            selector: Some("app=api".to_owned()),
+            field_selector: None,
+        });
+        let loading = Job::new(app.id(), JobKind::Pods, 1, || {
+            JobOutput::Pods(Ok(vec!["web-1   1/1   Running   0   2d".parse().unwrap()]))
+        });
+        let selected = Job::new(app.id(), JobKind::Pods, 2, || JobOutput::SelectedPods {
+            selector: Some("app=api".to_owned()),
+            field_selector: None,
+            pods: Ok(vec!["api-1   1/1   Running   0   2d".parse().unwrap()]),
+        });
+
+        // when
+        app.finish_job(selected.run()).unwrap();
+        app.finish_job(loading.run()).unwrap();
+
+        // then
+        assert_eq!(app.pod_selector(), Some("app=api"));
+        assert_eq!(app.get_pod_name(0), Some("api-1"));
+        assert_eq!(app.get_pods_number(), 1);
+    }
+
+    #[test]
+    fn apply_the_results_of_every_command() {
+        // given
+        let mut app = App::new("default".to_owned(), None, None, None);
+        let scale = |replicas| KubectlCommand::Scale {
+            name: "api".to_owned(),
+            replicas,
+        };
+        app.start_job(JobRequest::Command(scale(0)));
+        app.start_job(JobRequest::Command(scale(2)));
+        let failed = Job::new(app.id(), JobKind::Command, 1, move || {
+            let err = Error::KubectlFailed {
+                command: "scale deployment api --replicas=0".to_owned(),
+                code: Some(1),
+                stderr: "error: forbidden".to_owned(),
+            };
+            JobOutput::Command(scale(0), Err(err))
+        });
+        let scaled = Job::new(app.id(), JobKind::Command, 2, move || {
+            JobOutput::Command(scale(2), Ok("deployment.apps/api scaled\n".to_owned()))
+        });
+
+        // when
+        let after_second = app.finish_job(scaled.run()).unwrap();
+        let loading_after_second = app.is_loading(JobKind::Command);
+        let after_first = app.finish_job(failed.run());
+
+        // then
+        assert!(matches!(
+            after_second,
+            JobOutcome::Then(JobRequest::Deployments)
+        ));
+        assert!(loading_after_second);
+        assert!(
+            matches!(after_first, Err(Error::KubectlFailed { stderr, .. }) if stderr == "error: forbidden")
+        );
+        assert!(!app.is_loading(JobKind::Command));
+    }
+
+    #[test]
+    fn show_the_command_output_and_reload_what_it_changed() {
+        // given
+        let mut app = App::new("default".to_owned(), None, None, None);
+        app.resources = Some(ResourceTable::parse(
+            "configmaps",
+            "NAME   DATA   AGE\nsettings   2   3d\n",
+        ));
+        let scale = KubectlCommand::Scale {
+            name: "api".to_owned(),
+            replicas: 2,
+        };
+        let delete = KubectlCommand::Delete {
+            kind: "configmaps".to_owned(),
+            name: "settings".to_owned(),
+        };
+        app.start_job(JobRequest::Command(scale));
+        let scaled = Job::new(app.id(), JobKind::Command, 1, || {
+            let scale = KubectlCommand::Scale {
+                name: "api".to_owned(),
+                replicas: 2,
+            };
+            JobOutput::Command(scale, Ok("deployment.apps/api scaled\n".to_owned()))
+        });
+
+        // when
+        let after_scale = app.finish_job(scaled.run()).unwrap();
+        let title = app.command_output().map(|it| it.title().to_owned());
+        let after_delete = delete.reload(app.resources().map(|it| it.kind()));
+
+        // then
+        assert!(matches!(
+            after_scale,
+            JobOutcome::Then(JobRequest::Deployments)
+        ));
+        assert_eq!(title.as_deref(), Some("Scale: api"));
+        assert!(matches!(after_delete, Some(JobRequest::Resources(kind)) if kind == "configmaps"));
+    }
 }
@@ -0,0 +1,22 @@
+/// Output of the last command run against a resource, shown next to the resource details.
+pub struct CommandOutput {
+    title: String,
+    lines: Vec<String>,
+}
+
+impl CommandOutput {
+    pub fn new(title: String, output: &str) -> CommandOutput {
+        CommandOutput {
+            title,
+            lines: output.lines().map(|it| it.to_owned()).collect(),
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn lines(&self) -> &Vec<String> {
+        &self.lines
+    }
+}
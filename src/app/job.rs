@@ -0,0 +1,319 @@
+use std::{
+    any::Any,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+};
+
+use crate::{
+    cluster_event::ClusterEvent, delete_resource, deployment::Deployment, describe_resource,
+    errors::Error, manifest::Manifest, metrics::PodMetrics, node::Node, node::NodeOperation,
+    operate_on_node, pod::Pod, resource_table::ResourceTable, rollout_deployment, scale_deployment,
+};
+
+/// What a background job loads, its pane shows a spinner meanwhile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum JobKind {
+    Pods,
+    Logs,
+    Metrics,
+    Events,
+    Deployments,
+    Nodes,
+    Resources,
+    Manifest,
+    /// kubectl command whose output is shown next to the resource details.
+    Command,
+    /// Review or apply of the edited manifest.
+    Changes,
+    ResourceKinds,
+    Namespaces,
+    Contexts,
+}
+
+impl JobKind {
+    /// Each command changes something, so all their results are applied. Only the latest load
+    /// of the other kinds is, the older ones being stale.
+    pub fn keeps_every_result(&self) -> bool {
+        matches!(self, JobKind::Command | JobKind::Changes)
+    }
+
+    pub fn failure_title(&self) -> &'static str {
+        match self {
+            JobKind::Pods => "Couldn't load pods",
+            JobKind::Logs => "Couldn't load logs",
+            JobKind::Metrics => "Couldn't refresh metrics",
+            JobKind::Events => "Couldn't load events",
+            JobKind::Deployments => "Couldn't load deployments",
+            JobKind::Nodes => "Couldn't load nodes",
+            JobKind::Resources => "Couldn't load resources",
+            JobKind::Manifest => "Couldn't load the manifest",
+            JobKind::Command => "Command failed",
+            JobKind::Changes => "Couldn't apply the changes",
+            JobKind::ResourceKinds => "Couldn't discover resource kinds",
+            JobKind::Namespaces => "Couldn't load namespaces",
+            JobKind::Contexts => "Couldn't load contexts",
+        }
+    }
+}
+
+pub enum JobRequest {
+    Pods,
+    /// Pods with other selectors, which are kept only when kubectl accepts them.
+    SelectPods {
+        selector: Option<String>,
+        field_selector: Option<String>,
+    },
+    Logs {
+        pod_name: String,
+        since: Option<String>,
+    },
+    Metrics,
+    Events,
+    Deployments,
+    Nodes,
+    Resources(String),
+    Manifest {
+        kind: String,
+        name: String,
+    },
+    Command(KubectlCommand),
+    /// Validates the edited manifest in the file and diffs it against the live resource.
+    ReviewChanges(PathBuf),
+    ApplyChanges(PathBuf),
+    ResourceKinds,
+    Namespaces,
+    Contexts,
+}
+
+impl JobRequest {
+    pub fn kind(&self) -> JobKind {
+        match self {
+            JobRequest::Pods | JobRequest::SelectPods { .. } => JobKind::Pods,
+            JobRequest::Logs { .. } => JobKind::Logs,
+            JobRequest::Metrics => JobKind::Metrics,
+            JobRequest::Events => JobKind::Events,
+            JobRequest::Deployments => JobKind::Deployments,
+            JobRequest::Nodes => JobKind::Nodes,
+            JobRequest::Resources(_) => JobKind::Resources,
+            JobRequest::Manifest { .. } => JobKind::Manifest,
+            JobRequest::Command(_) => JobKind::Command,
+            JobRequest::ReviewChanges(_) | JobRequest::ApplyChanges(_) => JobKind::Changes,
+            JobRequest::ResourceKinds => JobKind::ResourceKinds,
+            JobRequest::Namespaces => JobKind::Namespaces,
+            JobRequest::Contexts => JobKind::Contexts,
+        }
+    }
+}
+
+/// Commands acting on a resource, their output replaces the command output of the app.
+pub enum KubectlCommand {
+    OperateOnNode(NodeOperation, String),
+    Scale {
+        name: String,
+        replicas: u32,
+    },
+    Rollout {
+        name: String,
+        subcommand: String,
+        args: Vec<String>,
+    },
+    Delete {
+        kind: String,
+        name: String,
+    },
+    Describe {
+        kind: String,
+        name: String,
+    },
+}
+
+impl KubectlCommand {
+    pub(super) fn run(&self, namespace: &str, context: Option<&str>) -> Result<String, Error> {
+        match self {
+            KubectlCommand::OperateOnNode(operation, name) => {
+                operate_on_node(*operation, name, context)
+            }
+            KubectlCommand::Scale { name, replicas } => {
+                scale_deployment(name, namespace, *replicas, context)
+            }
+            KubectlCommand::Rollout {
+                name,
+                subcommand,
+                args,
+            } => rollout_deployment(subcommand, name, namespace, args, context),
+            KubectlCommand::Delete { kind, name } => {
+                delete_resource(kind, name, namespace, context)
+            }
+            KubectlCommand::Describe { kind, name } => {
+                describe_resource(kind, name, namespace, context)
+            }
+        }
+    }
+
+    /// Title of the command output.
+    pub(super) fn title(&self) -> String {
+        match self {
+            KubectlCommand::OperateOnNode(operation, name) => {
+                format!("{}: {name}", operation.command())
+            }
+            KubectlCommand::Scale { name, .. } => format!("Scale: {name}"),
+            KubectlCommand::Rollout {
+                name, subcommand, ..
+            } => format!("Rollout {subcommand}: {name}"),
+            KubectlCommand::Delete { kind, name } => format!("Delete: {kind}/{name}"),
+            KubectlCommand::Describe { kind, name } => format!("Describe: {kind}/{name}"),
+        }
+    }
+
+    pub fn failure_title(&self) -> String {
+        match self {
+            KubectlCommand::OperateOnNode(operation, _) => {
+                format!("Node {} failed", operation.command())
+            }
+            KubectlCommand::Scale { .. } => "Scale failed".to_owned(),
+            KubectlCommand::Rollout { subcommand, .. } if subcommand == "restart" => {
+                "Restart failed".to_owned()
+            }
+            KubectlCommand::Rollout { subcommand, .. } => format!("Rollout {subcommand} failed"),
+            KubectlCommand::Delete { .. } => "Delete failed".to_owned(),
+            KubectlCommand::Describe { .. } => "Describe failed".to_owned(),
+        }
+    }
+
+    /// Job reloading what the command changed, `listed_kind` is the kind of the resources
+    /// listed by the app.
+    pub(super) fn reload(&self, listed_kind: Option<&str>) -> Option<JobRequest> {
+        match self {
+            KubectlCommand::OperateOnNode(_, _) => Some(JobRequest::Nodes),
+            KubectlCommand::Scale { .. } | KubectlCommand::Rollout { .. } => {
+                Some(JobRequest::Deployments)
+            }
+            KubectlCommand::Delete { kind, .. } if listed_kind == Some(kind.as_str()) => {
+                Some(JobRequest::Resources(kind.clone()))
+            }
+            KubectlCommand::Delete { kind, .. } if kind == "pod" => Some(JobRequest::Pods),
+            KubectlCommand::Delete { kind, .. } if kind == "deployment" => {
+                Some(JobRequest::Deployments)
+            }
+            KubectlCommand::Delete { .. } | KubectlCommand::Describe { .. } => None,
+        }
+    }
+}
+
+/// kubectl calls to run on a worker thread, with copies of what they need from the app.
+pub struct Job {
+    app_id: usize,
+    kind: JobKind,
+    number: u64,
+    run: Box<dyn FnOnce() -> JobOutput + Send>,
+}
+
+impl Job {
+    pub(super) fn new(
+        app_id: usize,
+        kind: JobKind,
+        number: u64,
+        run: impl FnOnce() -> JobOutput + Send + 'static,
+    ) -> Job {
+        Job {
+            app_id,
+            kind,
+            number,
+            run: Box::new(run),
+        }
+    }
+
+    /// A panic of the job is its failure, so its pane doesn't wait for it forever.
+    pub fn run(self) -> JobResult {
+        let output = panic::catch_unwind(AssertUnwindSafe(self.run))
+            .unwrap_or_else(|payload| JobOutput::Panicked(panic_message(payload)));
+        JobResult {
+            app_id: self.app_id,
+            kind: self.kind,
+            number: self.number,
+            output,
+        }
+    }
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => (*message).to_owned(),
+            Err(_) => "unknown panic".to_owned(),
+        },
+    }
+}
+
+/// Output of a finished job, to be applied to the app which started it.
+pub struct JobResult {
+    app_id: usize,
+    kind: JobKind,
+    pub(super) number: u64,
+    pub(super) output: JobOutput,
+}
+
+impl JobResult {
+    /// Result of a job which failed, for the tests of the UI.
+    #[cfg(test)]
+    pub(crate) fn failed(app_id: usize, kind: JobKind, number: u64, message: &str) -> JobResult {
+        JobResult {
+            app_id,
+            kind,
+            number,
+            output: JobOutput::Panicked(message.to_owned()),
+        }
+    }
+
+    pub fn app_id(&self) -> usize {
+        self.app_id
+    }
+
+    pub fn kind(&self) -> JobKind {
+        self.kind
+    }
+
+    /// Title of the popup or the status shown when the job fails.
+    pub fn failure_title(&self) -> String {
+        match &self.output {
+            JobOutput::Command(command, _) => command.failure_title(),
+            _ => self.kind.failure_title().to_owned(),
+        }
+    }
+}
+
+/// What is left to do once the result of a job is applied to the app.
+pub enum JobOutcome {
+    Done,
+    /// Job to start next, ex: reloading the deployments once one is scaled.
+    Then(JobRequest),
+    /// Diff of the edited manifest in the file, `None` when nothing has changed.
+    Reviewed(PathBuf, Result<Option<String>, Error>),
+    /// Output of kubectl once the edited manifest in the file is applied.
+    Applied(PathBuf, Result<String, Error>),
+}
+
+pub(super) enum JobOutput {
+    Pods(Result<Vec<Pod>, Error>),
+    SelectedPods {
+        selector: Option<String>,
+        field_selector: Option<String>,
+        pods: Result<Vec<Pod>, Error>,
+    },
+    Logs(String, Result<Vec<String>, Error>),
+    Metrics(Result<Vec<PodMetrics>, Error>),
+    Events(Result<Vec<ClusterEvent>, Error>),
+    Deployments(Result<Vec<Deployment>, Error>),
+    Nodes(Result<Vec<Node>, Error>),
+    Resources(Result<ResourceTable, Error>),
+    Manifest(Result<Manifest, Error>),
+    Command(KubectlCommand, Result<String, Error>),
+    Reviewed(PathBuf, Result<Option<String>, Error>),
+    /// The manifest is fetched again once applied.
+    Applied(PathBuf, Result<(String, Option<Manifest>), Error>),
+    ResourceKinds(Result<Vec<String>, Error>),
+    Namespaces(Result<Vec<String>, Error>),
+    Contexts(Result<Vec<String>, Error>),
+    Panicked(String),
+}
@@ -0,0 +1,64 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::metrics::PodMetrics;
+
+/// Number of samples kept per pod for the usage history.
+const HISTORY_SIZE: usize = 60;
+
+/// Latest metrics of the pods, with the usage history of each pod kept across refreshes.
+#[derive(Default)]
+pub struct MetricsKeeper {
+    metrics: HashMap<String, PodMetrics>,
+    cpu_history: HashMap<String, VecDeque<u64>>,
+    memory_history: HashMap<String, VecDeque<u64>>,
+}
+
+impl MetricsKeeper {
+    pub fn update(&mut self, metrics: Vec<PodMetrics>) {
+        self.metrics = metrics
+            .into_iter()
+            .map(|it| (it.name.clone(), it))
+            .collect();
+
+        for pod_metrics in self.metrics.values() {
+            if let Some(cpu) = pod_metrics.cpu {
+                push_sample(&mut self.cpu_history, &pod_metrics.name, cpu);
+            }
+            if let Some(memory) = pod_metrics.memory {
+                push_sample(&mut self.memory_history, &pod_metrics.name, memory);
+            }
+        }
+        // pods which are gone won't come back with the same name
+        self.cpu_history
+            .retain(|name, _| self.metrics.contains_key(name));
+        self.memory_history
+            .retain(|name, _| self.metrics.contains_key(name));
+    }
+
+    pub fn get(&self, pod_name: &str) -> Option<&PodMetrics> {
+        self.metrics.get(pod_name)
+    }
+
+    pub fn cpu_history(&self, pod_name: &str) -> Vec<u64> {
+        history(&self.cpu_history, pod_name)
+    }
+
+    pub fn memory_history(&self, pod_name: &str) -> Vec<u64> {
+        history(&self.memory_history, pod_name)
+    }
+}
+
+fn push_sample(history: &mut HashMap<String, VecDeque<u64>>, pod_name: &str, sample: u64) {
+    let samples = history.entry(pod_name.to_owned()).or_default();
+    if samples.len() == HISTORY_SIZE {
+        samples.pop_front();
+    }
+    samples.push_back(sample);
+}
+
+fn history(history: &HashMap<String, VecDeque<u64>>, pod_name: &str) -> Vec<u64> {
+    history
+        .get(pod_name)
+        .map(|it| it.iter().copied().collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,121 @@
+use std::str::FromStr;
+
+use crate::errors::Error;
+
+/// Columns requested from kubectl, the message goes last as the only one containing spaces.
+pub const EVENT_COLUMNS: &str = "LAST:.lastTimestamp,TIME:.eventTime,TYPE:.type,REASON:.reason,KIND:.involvedObject.kind,NAME:.involvedObject.name,COUNT:.count,MESSAGE:.message";
+
+const NONE: &str = "<none>";
+
+/// Kubernetes event, as listed by `kubectl get events` with the `EVENT_COLUMNS`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusterEvent {
+    pub last_seen: String,
+    pub event_type: String,
+    pub reason: String,
+    pub object: String,
+    pub count: u32,
+    pub message: String,
+}
+
+impl ClusterEvent {
+    pub fn is_warning(&self) -> bool {
+        self.event_type == "Warning"
+    }
+}
+
+impl FromStr for ClusterEvent {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let mut next = || parts.next().ok_or(Error::ParseOutputError);
+
+        let last_timestamp = next()?;
+        let event_time = next()?;
+        let event_type = next()?.to_string();
+        let reason = next()?.to_string();
+        let kind = next()?;
+        let name = next()?;
+        let count = next()?;
+        let message = parts.collect::<Vec<_>>().join(" ");
+
+        // Events reported through the events.k8s.io API only have the event time
+        let last_seen = match last_timestamp {
+            NONE => event_time,
+            timestamp => timestamp,
+        };
+        if last_seen == "LAST" {
+            return Err(Error::ParseOutputError);
+        }
+        let count = match count {
+            NONE => 1,
+            count => count.parse().map_err(|_| Error::ParseOutputError)?,
+        };
+
+        Ok(ClusterEvent {
+            last_seen: last_seen.replace('T', " ").replace('Z', ""),
+            event_type,
+            reason,
+            object: format!("{}/{}", kind.to_lowercase(), name),
+            count,
+            message,
+        })
+    }
+}
+
+/// Most recent events go first.
+pub fn sort_by_last_seen(events: &mut [ClusterEvent]) {
+    events.sort_by(|a, b| b.last_seen.cmp(&a.last_seen));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::Error;
+
+    use super::ClusterEvent;
+
+    #[test]
+    fn parse_event() {
+        //given
+        let event_info = "2022-11-02T10:15:00Z   <none>   Warning   BackOff   Pod   api-7dd394858c-sxctv   12   Back-off restarting failed container";
+        let expected = ClusterEvent {
+            last_seen: "2022-11-02 10:15:00".to_string(),
+            event_type: "Warning".to_string(),
+            reason: "BackOff".to_string(),
+            object: "pod/api-7dd394858c-sxctv".to_string(),
+            count: 12,
+            message: "Back-off restarting failed container".to_string(),
+        };
+
+        // when
+        let actual: ClusterEvent = event_info.parse().unwrap();
+
+        //then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_event_with_event_time_only() {
+        //given
+        let event_info = "<none>   2022-11-02T10:15:00.123456Z   Normal   Scheduled   Pod   api-1   <none>   Successfully assigned default/api-1 to node-1";
+
+        // when
+        let actual: ClusterEvent = event_info.parse().unwrap();
+
+        //then
+        assert_eq!(actual.last_seen, "2022-11-02 10:15:00.123456");
+        assert_eq!(actual.count, 1);
+        assert!(!actual.is_warning());
+    }
+
+    #[test]
+    fn should_not_parse_headers() {
+        //given
+        let header = "LAST   TIME   TYPE   REASON   KIND   NAME   COUNT   MESSAGE";
+        //when
+        let result: Result<ClusterEvent, Error> = header.parse();
+        // then expect error
+        result.unwrap_err();
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    collections::HashMap,
+    env,
+    fmt::Display,
+    fs,
+    io::ErrorKind,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    errors::Error,
+    input::keymap::KeymapFile,
+    pod::PodColumn,
+    ui::theme::{Theme, ThemeFile},
+};
+
+/// Widths the pods table can take, in percent of the body.
+pub const PODS_TABLE_WIDTHS: RangeInclusive<u16> = 10..=90;
+
+/// Settings read from `config.toml`, with the defaults for everything missing there, ex:
+///
+/// ```toml
+/// namespace = "dev"
+/// tick_rate_ms = 100
+/// theme = "light"
+///
+/// [contexts.production]
+/// namespace = "api"
+/// selector = "tier=backend"
+///
+/// [keymap.pods]
+/// "ctrl-l" = "fetch_logs"
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub namespace: String,
+    pub selector: Option<String>,
+    pub field_selector: Option<String>,
+    pub columns: Vec<PodColumn>,
+    /// How often the screen is redrawn when no key is pressed.
+    pub tick_rate_ms: u64,
+    pub events_refresh_secs: u64,
+    pub metrics_refresh_secs: u64,
+    /// Number of the most recent log lines shown.
+    pub log_lines: usize,
+    /// Widths of the lists on the left, in percent of the screen.
+    pub list_width: u16,
+    pub pods_table_width: u16,
+    /// Below this width the logs go under the pods table instead of next to it.
+    pub narrow_width: u16,
+    /// Name of a built-in theme, or of one of the `themes`.
+    pub theme: String,
+    pub themes: HashMap<String, ThemeFile>,
+    pub keymap: KeymapFile,
+    /// Overrides of the defaults for the kubectl contexts, by the context name.
+    pub contexts: HashMap<String, ContextConfig>,
+    /// kubectl context used when a workspace has none, it isn't read from the file.
+    #[serde(skip)]
+    pub current_context: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            namespace: "default".to_owned(),
+            selector: None,
+            field_selector: None,
+            columns: PodColumn::ALL.to_vec(),
+            tick_rate_ms: 200,
+            events_refresh_secs: 2,
+            metrics_refresh_secs: 15,
+            log_lines: 50,
+            list_width: 20,
+            pods_table_width: 45,
+            narrow_width: 120,
+            theme: "dark".to_owned(),
+            themes: HashMap::new(),
+            keymap: KeymapFile::default(),
+            contexts: HashMap::new(),
+            current_context: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ContextConfig {
+    pub namespace: Option<String>,
+    pub selector: Option<String>,
+    pub field_selector: Option<String>,
+}
+
+impl Config {
+    /// Reads the config file from the config directory, the defaults are used when there is none.
+    pub fn load() -> Result<Config, Error> {
+        Config::load_from(&config_dir().join("config.toml"))
+    }
+
+    /// Only a missing file means the defaults, it can't be read for any other reason.
+    pub fn load_from(path: &Path) -> Result<Config, Error> {
+        match fs::read_to_string(path) {
+            Ok(content) => Config::parse(&content).map_err(|err| file_error(path, err)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(err) => Err(file_error(path, err)),
+        }
+    }
+
+    /// Widths out of their range are brought back into it.
+    pub fn parse(content: &str) -> Result<Config, Error> {
+        let mut config: Config =
+            toml::from_str(content).map_err(|err| Error::InvalidConfig(err.to_string()))?;
+        config.pods_table_width = config
+            .pods_table_width
+            .clamp(*PODS_TABLE_WIDTHS.start(), *PODS_TABLE_WIDTHS.end());
+        Ok(config)
+    }
+
+    /// Defaults of the kubectl context take precedence over the general ones.
+    pub fn apply_context(&mut self, context: &str) {
+        let Some(context) = self.contexts.get(context).cloned() else {
+            return;
+        };
+        if let Some(namespace) = context.namespace {
+            self.namespace = namespace;
+        }
+        if context.selector.is_some() {
+            self.selector = context.selector;
+        }
+        if context.field_selector.is_some() {
+            self.field_selector = context.field_selector;
+        }
+    }
+
+    /// Config of a workspace on the context, `None` being the current one. Only the defaults of
+    /// that context are applied, the config itself keeps the general ones.
+    pub fn for_context(&self, context: Option<&str>) -> Config {
+        let mut config = self.clone();
+        if let Some(context) = context.or(self.current_context.as_deref()) {
+            config.apply_context(context);
+        }
+        config
+    }
+
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).unwrap_or_else(|err| format!("# {err}"))
+    }
+
+    pub fn theme(&self) -> Result<Theme, Error> {
+        Theme::load(&self.theme, &self.themes)
+    }
+
+    pub fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.tick_rate_ms)
+    }
+
+    pub fn events_refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.events_refresh_secs)
+    }
+
+    pub fn metrics_refresh_interval(&self) -> Duration {
+        Duration::from_secs(self.metrics_refresh_secs)
+    }
+}
+
+fn file_error(path: &Path, err: impl Display) -> Error {
+    Error::InvalidConfig(format!("{}: {err}", path.display()))
+}
+
+/// `$XDG_CONFIG_HOME/kuber`, or `~/.config/kuber` when it isn't set.
+pub fn config_dir() -> PathBuf {
+    let base = match env::var_os("XDG_CONFIG_HOME").filter(|it| !it.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env::var_os("HOME").unwrap_or_default()).join(".config"),
+    };
+    base.join("kuber")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pod::PodColumn;
+
+    use super::Config;
+
+    #[test]
+    fn use_the_defaults_only_without_a_file() {
+        //given
+        let dir = tempfile::tempdir().unwrap();
+
+        // when
+        let missing = Config::load_from(&dir.path().join("config.toml"));
+        let unreadable = Config::load_from(dir.path());
+
+        //then
+        assert_eq!(missing.unwrap(), Config::default());
+        unreadable.unwrap_err();
+    }
+
+    #[test]
+    fn fill_missing_values_with_defaults() {
+        //given
+        let content = r#"
+namespace = "dev"
+columns = ["name", "status", "ip"]
+
+[keymap]
+vim = true
+"#;
+
+        // when
+        let config = Config::parse(content).unwrap();
+
+        //then
+        assert_eq!(config.namespace, "dev");
+        assert_eq!(
+            config.columns,
+            vec![PodColumn::Name, PodColumn::Status, PodColumn::Ip]
+        );
+        assert!(config.keymap.vim);
+        assert_eq!(config.tick_rate_ms, 200);
+    }
+
+    #[test]
+    fn override_defaults_for_the_context() {
+        //given
+        let content = r#"
+namespace = "dev"
+selector = "app=api"
+
+[contexts.production]
+namespace = "api"
+"#;
+        let mut config = Config::parse(content).unwrap();
+
+        // when
+        config.apply_context("production");
+
+        //then
+        assert_eq!(config.namespace, "api");
+        assert_eq!(config.selector.as_deref(), Some("app=api"));
+    }
+
+    #[test]
+    fn apply_the_defaults_of_the_current_context_when_there_is_none() {
+        //given
+        let mut config =
+            Config::parse("[contexts.production]\nselector = \"tier=backend\"").unwrap();
+        config.current_context = Some("production".to_owned());
+
+        // when
+        let current = config.for_context(None);
+        let production = config.for_context(Some("production"));
+
+        //then
+        assert_eq!(config.selector, None);
+        assert_eq!(current.selector.as_deref(), Some("tier=backend"));
+        assert_eq!(production.selector.as_deref(), Some("tier=backend"));
+    }
+
+    #[test]
+    fn keep_the_defaults_of_other_contexts_out() {
+        //given
+        let content = r#"
+[contexts.A]
+namespace = "api"
+selector = "tier=backend"
+
+[contexts.B]
+field_selector = "status.phase=Running"
+"#;
+        let mut config = Config::parse(content).unwrap();
+        config.current_context = Some("A".to_owned());
+
+        // when
+        let b = config.for_context(Some("B"));
+
+        //then
+        assert_eq!(b.namespace, "default");
+        assert_eq!(b.selector, None);
+        assert_eq!(b.field_selector.as_deref(), Some("status.phase=Running"));
+    }
+
+    #[test]
+    fn keep_the_pods_table_width_within_the_screen() {
+        assert_eq!(
+            Config::parse("pods_table_width = 65535")
+                .unwrap()
+                .pods_table_width,
+            90
+        );
+        assert_eq!(
+            Config::parse("pods_table_width = 0")
+                .unwrap()
+                .pods_table_width,
+            10
+        );
+    }
+
+    #[test]
+    fn print_config_which_can_be_read_back() {
+        let config = Config::default();
+
+        assert_eq!(Config::parse(&config.to_toml()).unwrap(), config);
+    }
+
+    #[test]
+    fn reject_unknown_settings() {
+        Config::parse("tick_rate = 100").unwrap_err();
+    }
+}
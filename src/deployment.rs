@@ -0,0 +1,90 @@
+use std::str::FromStr;
+
+use crate::errors::Error;
+
+/// Deployment as listed by `kubectl get deployments -o wide`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deployment {
+    pub name: String,
+    pub ready: String,
+    pub up_to_date: String,
+    pub available: String,
+    pub age: String,
+    pub selector: String,
+}
+
+impl FromStr for Deployment {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split_whitespace().collect();
+
+        // The selector comes last and may contain spaces, ex: `app in (a, b)`
+        if parts.len() < 8 {
+            return Err(Error::ParseOutputError);
+        };
+
+        // Should be numbers of replicas
+        if parts[2].parse::<u32>().is_err() || parts[3].parse::<u32>().is_err() {
+            return Err(Error::ParseOutputError);
+        }
+
+        Ok(Deployment {
+            name: parts[0].to_string(),
+            ready: parts[1].to_string(),
+            up_to_date: parts[2].to_string(),
+            available: parts[3].to_string(),
+            age: parts[4].to_string(),
+            selector: parts[7..].join(" "),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::Error;
+
+    use super::Deployment;
+
+    #[test]
+    fn parse_deployment_info() {
+        //given
+        let deployment_info = "api   2/3     3            2           12d   api   registry/api:1.2   app=api,tier=backend";
+        let expected = Deployment {
+            name: "api".to_string(),
+            ready: "2/3".to_string(),
+            up_to_date: "3".to_string(),
+            available: "2".to_string(),
+            age: "12d".to_string(),
+            selector: "app=api,tier=backend".to_string(),
+        };
+
+        // when
+        let actual: Deployment = deployment_info.parse().unwrap();
+
+        //then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn parse_selector_with_spaces() {
+        //given
+        let deployment_info = "web   1/1     1            1           3d    web   registry/web:2   app in (a, b),tier!=cache";
+
+        // when
+        let actual: Deployment = deployment_info.parse().unwrap();
+
+        //then
+        assert_eq!(actual.selector, "app in (a, b),tier!=cache");
+    }
+
+    #[test]
+    fn should_not_parse_headers() {
+        //given
+        let header = "NAME   READY   UP-TO-DATE   AVAILABLE   AGE   CONTAINERS   IMAGES   SELECTOR";
+        //when
+        let result: Result<Deployment, Error> = header.parse();
+        // then expect error
+        result.unwrap_err();
+    }
+}
@@ -0,0 +1,77 @@
+use std::{
+    env,
+    io::Write,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use tempfile::Builder;
+
+use crate::{errors::Error, manifest::Manifest};
+
+const DEFAULT_EDITOR: &str = "vi";
+
+/// Dumps the manifest to a temporary file which can be opened in an editor. The file gets a
+/// random name and is created exclusively, so nothing else can be swapped in its place.
+pub fn write_temp_file(manifest: &Manifest) -> Result<PathBuf, Error> {
+    let mut file = Builder::new()
+        .prefix(&format!("kuber-{}-{}-", manifest.kind(), manifest.name()))
+        .suffix(".yaml")
+        .tempfile()?;
+    file.write_all(manifest.yaml().as_bytes())?;
+    let (_, path) = file.keep().map_err(|err| err.error)?;
+    Ok(path)
+}
+
+/// Opens the file in `$VISUAL`/`$EDITOR` and waits until the editor is closed.
+/// The terminal has to be handed over to the editor before calling it.
+pub fn edit_file(path: &Path) -> Result<(), Error> {
+    let editor = editor_command();
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or(DEFAULT_EDITOR);
+
+    let status = Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .map_err(|_| Error::EditorFailed)?;
+    if !status.success() {
+        return Err(Error::EditorFailed);
+    }
+    Ok(())
+}
+
+fn editor_command() -> String {
+    env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .ok()
+        .filter(|it| !it.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_EDITOR.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn write_each_manifest_to_a_new_file() {
+        // given
+        let manifest = Manifest::new("pod", "api-1", "kind: Pod\n");
+
+        // when
+        let first = write_temp_file(&manifest).unwrap();
+        let second = write_temp_file(&manifest).unwrap();
+
+        // then
+        assert_ne!(first, second);
+        assert_eq!(fs::read_to_string(&first).unwrap(), manifest.yaml());
+        assert!(first
+            .file_name()
+            .and_then(|it| it.to_str())
+            .is_some_and(|it| it.starts_with("kuber-pod-api-1-") && it.ends_with(".yaml")));
+        fs::remove_file(first).unwrap();
+        fs::remove_file(second).unwrap();
+    }
+}
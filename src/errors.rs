@@ -8,4 +8,58 @@ pub enum Error {
     KubecltNotFound(#[from] io::Error),
     #[error("error parsing kubectl output")]
     ParseOutputError,
+    #[error("kubectl {command} failed with {}: {stderr}", exit_status(.code))]
+    KubectlFailed {
+        /// Arguments of the command.
+        command: String,
+        /// `None` when kubectl was killed by a signal.
+        code: Option<i32>,
+        stderr: String,
+    },
+    #[error("couldn't edit the file, check $EDITOR")]
+    EditorFailed,
+    #[error("unknown column {0}")]
+    UnknownColumn(String),
+    #[error("invalid keymap: {0}")]
+    InvalidKeymap(String),
+    #[error("invalid config: {0}")]
+    InvalidConfig(String),
+    #[error("invalid theme: {0}")]
+    InvalidTheme(String),
+    #[error("invalid command: {0}")]
+    InvalidCommand(String),
+    #[error("the background job panicked: {0}")]
+    JobPanicked(String),
+}
+
+fn exit_status(code: &Option<i32>) -> String {
+    match code {
+        Some(code) => format!("exit code {code}"),
+        None => "a signal".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn describe_failed_kubectl_commands() {
+        // given
+        let error = Error::KubectlFailed {
+            command: "get pods -n default".to_owned(),
+            code: Some(1),
+            stderr: "error: You must be logged in to the server (Unauthorized)".to_owned(),
+        };
+
+        // when
+        let message = error.to_string();
+
+        // then
+        assert_eq!(
+            message,
+            "kubectl get pods -n default failed with exit code 1: \
+             error: You must be logged in to the server (Unauthorized)"
+        );
+    }
 }
@@ -0,0 +1,66 @@
+/// Characters of the text matched by the query, as char indices.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FuzzyMatch {
+    pub positions: Vec<usize>,
+}
+
+impl FuzzyMatch {
+    pub fn is_matched(&self, position: usize) -> bool {
+        self.positions.binary_search(&position).is_ok()
+    }
+}
+
+/// Case insensitive match of the query characters in order, not necessarily next to each other.
+/// A continuous occurrence of the query is preferred, so highlights don't get scattered.
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    if query.is_empty() {
+        return Some(FuzzyMatch::default());
+    }
+
+    if let Some(start) = text.windows(query.len()).position(|it| it == query) {
+        return Some(FuzzyMatch {
+            positions: (start..start + query.len()).collect(),
+        });
+    }
+
+    let mut positions = Vec::with_capacity(query.len());
+    let mut query_chars = query.iter().peekable();
+    for (position, c) in text.iter().enumerate() {
+        if query_chars.peek() == Some(&c) {
+            positions.push(position);
+            query_chars.next();
+        }
+    }
+
+    match query_chars.peek() {
+        Some(_) => None,
+        None => Some(FuzzyMatch { positions }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_match;
+
+    #[test]
+    fn match_scattered_characters() {
+        let actual = fuzzy_match("apsx", "api-7dd394858c-sxctv").unwrap();
+
+        assert_eq!(actual.positions, vec![0, 1, 15, 16]);
+    }
+
+    #[test]
+    fn prefer_continuous_occurrence() {
+        let actual = fuzzy_match("SXC", "api-7dd394858c-sxctv").unwrap();
+
+        assert_eq!(actual.positions, vec![15, 16, 17]);
+    }
+
+    #[test]
+    fn reject_characters_out_of_order() {
+        assert_eq!(fuzzy_match("ipa", "api-7dd394858c-sxctv"), None);
+    }
+}
@@ -1,31 +1,176 @@
-use crossterm::event::{KeyCode, KeyEvent};
+use crate::ui::View;
 
-use self::event_loop::Event;
+use self::{event_loop::Event, keymap::Keymap};
 
+pub mod command;
 pub mod event_loop;
+pub mod keymap;
 
-pub enum InputAction {
-    Quit,
-    NextPod,
-    PreviousPod,
-    FetchLogs,
-    LogsUp,
-    LogsDown,
-    LogsEnd,
+/// Declares the actions together with the names used in the keymap file, their categories and
+/// descriptions, so the footer and the help can't drift from what the keys really do.
+macro_rules! input_actions {
+    ($($action:ident => $name:literal, $category:ident, $description:literal;)*) => {
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub enum InputAction {
+            $($action,)*
+        }
+
+        impl InputAction {
+            pub const ALL: &'static [InputAction] = &[$(InputAction::$action,)*];
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $(InputAction::$action => $name,)*
+                }
+            }
+
+            pub fn from_name(name: &str) -> Option<InputAction> {
+                match name {
+                    $($name => Some(InputAction::$action),)*
+                    _ => None,
+                }
+            }
+
+            pub fn category(&self) -> ActionCategory {
+                match self {
+                    $(InputAction::$action => ActionCategory::$category,)*
+                }
+            }
+
+            pub fn description(&self) -> &'static str {
+                match self {
+                    $(InputAction::$action => $description,)*
+                }
+            }
+        }
+    };
+}
+
+/// Groups of actions in the help, in the order they are listed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ActionCategory {
+    General,
+    Views,
+    Navigation,
+    Pods,
+    Logs,
+    Deployments,
+    Resources,
+    Events,
+    Nodes,
+    Manifest,
+    Workspaces,
+}
+
+impl ActionCategory {
+    pub fn title(&self) -> &'static str {
+        match self {
+            ActionCategory::General => "General",
+            ActionCategory::Views => "Views",
+            ActionCategory::Navigation => "Navigation",
+            ActionCategory::Pods => "Pods",
+            ActionCategory::Logs => "Logs",
+            ActionCategory::Deployments => "Deployments",
+            ActionCategory::Resources => "Resources",
+            ActionCategory::Events => "Events",
+            ActionCategory::Nodes => "Nodes",
+            ActionCategory::Manifest => "Manifest",
+            ActionCategory::Workspaces => "Workspaces",
+        }
+    }
+}
+
+input_actions! {
+    Quit => "quit", General, "quit";
+    ShowHelp => "show_help", General, "help";
+    OpenCommandPrompt => "command_prompt", General, "command";
+    Suspend => "suspend", General, "suspend";
+    FirstItem => "first_item", Navigation, "first";
+    LastItem => "last_item", Navigation, "last";
+    NextPane => "next_pane", Navigation, "next pane";
+    PreviousPane => "previous_pane", Navigation, "previous pane";
+    Up => "up", Navigation, "up";
+    Down => "down", Navigation, "down";
+    PageUp => "page_up", Navigation, "page up";
+    PageDown => "page_down", Navigation, "page down";
+    FetchLogs => "fetch_logs", Logs, "fetch logs";
+    LogsEnd => "logs_end", Logs, "logs end";
+    ToggleMaximisedLogs => "maximise_logs", Logs, "maximise logs";
+    ShowManifest => "show_manifest", Manifest, "manifest";
+    CloseManifest => "close_manifest", Manifest, "close";
+    ManifestUp => "manifest_up", Navigation, "up";
+    ManifestDown => "manifest_down", Navigation, "down";
+    ManifestPageUp => "manifest_page_up", Navigation, "page up";
+    ManifestPageDown => "manifest_page_down", Navigation, "page down";
+    ToggleFold => "toggle_fold", Manifest, "fold";
+    ToggleNoise => "toggle_noise", Manifest, "hide noise";
+    SearchManifest => "search_manifest", Manifest, "search";
+    NextMatch => "next_match", Manifest, "next match";
+    PreviousMatch => "previous_match", Manifest, "previous match";
+    EditManifest => "edit_manifest", Manifest, "edit";
+    NewWorkspace => "new_workspace", Workspaces, "new workspace";
+    CloseWorkspace => "close_workspace", Workspaces, "close workspace";
+    NextWorkspace => "next_workspace", Workspaces, "next workspace";
+    PreviousWorkspace => "previous_workspace", Workspaces, "previous workspace";
+    ShowPods => "show_pods", Views, "pods";
+    ShowDeployments => "show_deployments", Views, "deployments";
+    NextDeployment => "next_deployment", Navigation, "next deployment";
+    PreviousDeployment => "previous_deployment", Navigation, "previous deployment";
+    ScaleDeployment => "scale_deployment", Deployments, "scale";
+    RestartDeployment => "restart_deployment", Deployments, "restart";
+    RolloutStatus => "rollout_status", Deployments, "status";
+    RolloutHistory => "rollout_history", Deployments, "history";
+    UndoRollout => "undo_rollout", Deployments, "undo";
+    ShowDeploymentPods => "show_deployment_pods", Deployments, "show pods";
+    ClearPodSelector => "clear_pod_selector", Pods, "clear selectors";
+    ShowResources => "show_resources", Views, "resources";
+    PickResourceKind => "pick_resource_kind", Resources, "pick kind";
+    NextResource => "next_resource", Navigation, "next resource";
+    PreviousResource => "previous_resource", Navigation, "previous resource";
+    DescribeResource => "describe_resource", Resources, "describe";
+    ShowEvents => "show_events", Views, "events";
+    ShowPodEvents => "show_pod_events", Pods, "pod events";
+    FilterPods => "filter_pods", Pods, "filter";
+    SortPods => "sort_pods", Pods, "sort";
+    ReverseSortPods => "reverse_sort_pods", Pods, "reverse sort";
+    PickPodColumns => "pick_pod_columns", Pods, "columns";
+    GrowPodList => "grow_pod_list", Pods, "grow list";
+    ShrinkPodList => "shrink_pod_list", Pods, "shrink list";
+    SetLabelSelector => "set_label_selector", Pods, "label selector";
+    SetFieldSelector => "set_field_selector", Pods, "field selector";
+    NextEvent => "next_event", Navigation, "next event";
+    PreviousEvent => "previous_event", Navigation, "previous event";
+    ToggleEventsFilter => "toggle_events_filter", Events, "namespace/pod events";
+    ShowNodes => "show_nodes", Views, "nodes";
+    NextNode => "next_node", Navigation, "next node";
+    PreviousNode => "previous_node", Navigation, "previous node";
+    CordonNode => "cordon_node", Nodes, "cordon";
+    UncordonNode => "uncordon_node", Nodes, "uncordon";
+    DrainNode => "drain_node", Nodes, "drain";
+    ShowNodePods => "show_node_pods", Nodes, "show pods";
 }
 
-pub fn map_input(input: Event<KeyEvent>) -> Option<InputAction> {
+pub fn map_input<T>(input: Event<T>, view: &View, keymap: &Keymap) -> Option<InputAction> {
     match input {
-        Event::Input(event) => match event.code {
-            KeyCode::Char('q') | KeyCode::F(12) => Some(InputAction::Quit),
-            KeyCode::Down => Some(InputAction::NextPod),
-            KeyCode::Up => Some(InputAction::PreviousPod),
-            KeyCode::Enter => Some(InputAction::FetchLogs),
-            KeyCode::PageUp => Some(InputAction::LogsUp),
-            KeyCode::PageDown => Some(InputAction::LogsDown),
-            KeyCode::End => Some(InputAction::LogsEnd),
-            _ => None,
-        },
-        Event::Tick => None,
+        Event::Input(event) => keymap.action(view, event),
+        Event::Mouse(_) | Event::Signal(_) | Event::Tick | Event::Worker(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn find_every_action_by_its_unique_name() {
+        let names: HashSet<_> = InputAction::ALL.iter().map(|it| it.name()).collect();
+
+        assert_eq!(names.len(), InputAction::ALL.len());
+        for action in InputAction::ALL {
+            assert_eq!(InputAction::from_name(action.name()), Some(*action));
+        }
+        assert_eq!(InputAction::from_name("explode"), None);
     }
 }
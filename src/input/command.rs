@@ -0,0 +1,218 @@
+use std::str::FromStr;
+
+use crate::errors::Error;
+
+use super::InputAction::{self, *};
+
+/// Command typed after `:`, ex: `ns kube-system` or `logs --since 10m`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    /// Any action, by its keymap name or one of the aliases.
+    Action(InputAction),
+    Namespace(String),
+    Context(String),
+    /// Logs of the pod, the selected one when no name is given.
+    Logs {
+        pod_name: Option<String>,
+        since: Option<String>,
+    },
+    /// Deletes the item of the view, the selected one when no name is given.
+    Delete(Option<String>),
+}
+
+/// Commands taking arguments.
+const COMMANDS: &[&str] = &["ns", "ctx", "logs", "delete"];
+
+/// Short names of the actions, following the kubectl ones where there is one.
+const ALIASES: &[(&str, InputAction)] = &[
+    ("q", Quit),
+    ("quit", Quit),
+    ("help", ShowHelp),
+    ("po", ShowPods),
+    ("pods", ShowPods),
+    ("deploy", ShowDeployments),
+    ("deployments", ShowDeployments),
+    ("res", ShowResources),
+    ("resources", ShowResources),
+    ("ev", ShowEvents),
+    ("events", ShowEvents),
+    ("no", ShowNodes),
+    ("nodes", ShowNodes),
+];
+
+impl Command {
+    /// Everything which can be typed as the first word, for the completion.
+    pub fn names() -> Vec<String> {
+        let mut names: Vec<String> = COMMANDS
+            .iter()
+            .copied()
+            .chain(ALIASES.iter().map(|(alias, _)| *alias))
+            .chain(InputAction::ALL.iter().map(|it| it.name()))
+            .map(|it| it.to_owned())
+            .collect();
+        // Some aliases are also action names
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+impl FromStr for Command {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Command, Error> {
+        let words: Vec<&str> = input.split_whitespace().collect();
+        let Some((name, args)) = words.split_first() else {
+            return Err(invalid("type a command, ex: ns kube-system"));
+        };
+
+        match (*name, args) {
+            ("ns", [namespace]) => Ok(Command::Namespace(namespace.to_string())),
+            ("ns", _) => Err(invalid("usage: ns <namespace>")),
+            ("ctx", [context]) => Ok(Command::Context(context.to_string())),
+            ("ctx", _) => Err(invalid("usage: ctx <context>")),
+            ("logs", args) => parse_logs(args),
+            ("delete", []) => Ok(Command::Delete(None)),
+            ("delete", [name]) => Ok(Command::Delete(Some(name.to_string()))),
+            ("delete", _) => Err(invalid("usage: delete [name]")),
+            (name, args) => {
+                let action = ALIASES
+                    .iter()
+                    .find(|(alias, _)| *alias == name)
+                    .map(|(_, action)| *action)
+                    .or_else(|| InputAction::from_name(name))
+                    .ok_or_else(|| invalid(&format!("unknown command {name}")))?;
+                match args.is_empty() {
+                    true => Ok(Command::Action(action)),
+                    false => Err(invalid(&format!("{name} takes no arguments"))),
+                }
+            }
+        }
+    }
+}
+
+/// `logs [pod] [--since <duration>]`, the duration can also follow `--since=`.
+fn parse_logs(args: &[&str]) -> Result<Command, Error> {
+    let mut pod_name = None;
+    let mut since = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--since" => match args.next() {
+                Some(duration) => since = Some(duration.to_string()),
+                None => return Err(invalid("--since needs a duration, ex: 10m")),
+            },
+            arg if arg.starts_with("--since=") => since = Some(arg["--since=".len()..].to_owned()),
+            arg if arg.starts_with('-') => return Err(invalid(&format!("unknown option {arg}"))),
+            arg if pod_name.is_none() => pod_name = Some(arg.to_owned()),
+            _ => return Err(invalid("usage: logs [pod] [--since <duration>]")),
+        }
+    }
+    Ok(Command::Logs { pod_name, since })
+}
+
+fn invalid(message: &str) -> Error {
+    Error::InvalidCommand(message.to_owned())
+}
+
+/// Words before the one being typed, the command name is completed when there are none.
+pub fn completed_words(input: &str) -> Vec<&str> {
+    let mut words: Vec<&str> = input.split_whitespace().collect();
+    if !input.ends_with(' ') {
+        words.pop();
+    }
+    words
+}
+
+/// Completes the last word with the candidates starting with it, as far as they agree, and
+/// moves on to the next word when only one is left.
+pub fn complete(input: &str, candidates: &[String]) -> String {
+    let start = input.rfind(' ').map(|it| it + 1).unwrap_or(0);
+    let (head, word) = input.split_at(start);
+    let matching: Vec<&str> = candidates
+        .iter()
+        .map(|it| it.as_str())
+        .filter(|it| it.starts_with(word))
+        .collect();
+
+    match matching.as_slice() {
+        [] => input.to_owned(),
+        [only] => format!("{head}{only} "),
+        [first, rest @ ..] => {
+            let prefix = rest
+                .iter()
+                .fold(*first, |prefix, it| common_prefix(prefix, it));
+            format!("{head}{prefix}")
+        }
+    }
+}
+
+fn common_prefix<'a>(a: &'a str, b: &str) -> &'a str {
+    let end = a
+        .char_indices()
+        .zip(b.chars())
+        .find(|((_, a), b)| a != b)
+        .map(|((index, _), _)| index)
+        .unwrap_or(a.len().min(b.len()));
+    &a[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_commands() {
+        assert_eq!(
+            "ns kube-system".parse::<Command>().unwrap(),
+            Command::Namespace("kube-system".to_owned())
+        );
+        assert_eq!(
+            "deploy".parse::<Command>().unwrap(),
+            Command::Action(ShowDeployments)
+        );
+        assert_eq!(
+            "fetch_logs".parse::<Command>().unwrap(),
+            Command::Action(FetchLogs)
+        );
+        assert_eq!(
+            "logs api-1 --since 10m".parse::<Command>().unwrap(),
+            Command::Logs {
+                pod_name: Some("api-1".to_owned()),
+                since: Some("10m".to_owned())
+            }
+        );
+        assert_eq!("delete".parse::<Command>().unwrap(), Command::Delete(None));
+
+        "ns".parse::<Command>().unwrap_err();
+        "explode".parse::<Command>().unwrap_err();
+        "logs --since".parse::<Command>().unwrap_err();
+    }
+
+    #[test]
+    fn complete_the_last_word() {
+        // given
+        let candidates = vec![
+            "kube-system".to_owned(),
+            "kube-public".to_owned(),
+            "default".to_owned(),
+        ];
+
+        // when
+        let shared = complete("ns ku", &candidates);
+        let single = complete("ns d", &candidates);
+        let none = complete("ns x", &candidates);
+
+        // then
+        assert_eq!(shared, "ns kube-");
+        assert_eq!(single, "ns default ");
+        assert_eq!(none, "ns x");
+    }
+
+    #[test]
+    fn find_the_completed_words() {
+        assert!(completed_words("ns").is_empty());
+        assert_eq!(completed_words("ns "), vec!["ns"]);
+        assert_eq!(completed_words("logs api --si"), vec!["logs", "api"]);
+    }
+}
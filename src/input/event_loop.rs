@@ -1,54 +1,312 @@
 use std::{
-    sync::mpsc::{self, Receiver, RecvError},
-    thread,
+    io,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver, RecvError, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
     time::{Duration, Instant},
 };
 
-use crossterm::{
-    event::{self, Event as CEvent, KeyEvent},
-    terminal::enable_raw_mode,
+use crossterm::event::{self, Event as CEvent, KeyEvent, MouseEvent};
+use signal_hook::{
+    consts::{SIGINT, SIGTERM, SIGTSTP},
+    iterator::{Handle, Signals},
 };
 
+const PAUSED_CHECK_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Events of the terminal, or `Worker` ones posted by the background workers.
 pub enum Event<T> {
-    Input(T),
+    Input(KeyEvent),
+    Mouse(MouseEvent),
+    /// SIGINT, SIGTERM or SIGTSTP, left to the UI so it can restore the terminal first.
+    Signal(i32),
     Tick,
+    Worker(T),
 }
 
-pub struct EventLoop {
-    rx: Receiver<Event<KeyEvent>>,
+pub struct EventLoop<T> {
+    tx: Sender<Event<T>>,
+    rx: Receiver<Event<T>>,
+    paused: Arc<AtomicBool>,
+    polling: Arc<Mutex<()>>,
+    shutdown: Arc<AtomicBool>,
+    signals: Option<Handle>,
+    threads: Vec<JoinHandle<()>>,
 }
 
-impl EventLoop {
-    pub fn start(tick_rate: Duration) -> EventLoop {
-        enable_raw_mode().expect("can run in raw mode");
-        let (tx, rx) = mpsc::channel();
+impl<T: Send + 'static> EventLoop<T> {
+    /// Reads the terminal and forwards the signals until the loop is shut down.
+    pub fn start(tick_rate: Duration) -> EventLoop<T> {
+        let mut event_loop = EventLoop::new();
 
-        thread::spawn(move || keep_emitting_events(tick_rate, tx));
-        EventLoop { rx }
+        // Without the handlers the signals keep their default behaviour
+        if let Ok(signals) = Signals::new([SIGINT, SIGTERM, SIGTSTP]) {
+            event_loop.signals = Some(signals.handle());
+            let signals_tx = event_loop.tx.clone();
+            event_loop
+                .threads
+                .push(thread::spawn(move || forward_signals(signals, signals_tx)));
+        }
+
+        event_loop.add_source(tick_rate, read_terminal);
+        event_loop
     }
 
-    pub fn next(&self) -> Result<Event<KeyEvent>, RecvError> {
+    /// Polls the source for events, with the ticks in between, while the loop isn't paused.
+    /// The source gets how long it may wait for an event, and stops the polling with an error.
+    fn add_source<S>(&mut self, tick_rate: Duration, source: S)
+    where
+        S: FnMut(Duration) -> io::Result<Option<Event<T>>> + Send + 'static,
+    {
+        let tx = self.tx.clone();
+        let paused = self.paused.clone();
+        let polling = self.polling.clone();
+        let shutdown = self.shutdown.clone();
+        self.threads.push(thread::spawn(move || {
+            keep_emitting_events(tick_rate, tx, paused, polling, shutdown, source)
+        }));
+    }
+}
+
+impl<T> EventLoop<T> {
+    /// Loop without any source of events, only the workers post into it.
+    pub(crate) fn new() -> EventLoop<T> {
+        let (tx, rx) = mpsc::channel();
+        EventLoop {
+            tx,
+            rx,
+            paused: Arc::new(AtomicBool::new(false)),
+            polling: Arc::new(Mutex::new(())),
+            shutdown: Arc::new(AtomicBool::new(false)),
+            signals: None,
+            threads: vec![],
+        }
+    }
+
+    pub fn next(&self) -> Result<Event<T>, RecvError> {
         self.rx.recv()
     }
+
+    /// Sender for a background worker, whose events come out of `next` with the others.
+    pub fn sender(&self) -> EventSender<T> {
+        EventSender {
+            tx: self.tx.clone(),
+            shutdown: self.shutdown.clone(),
+        }
+    }
+
+    /// Stops reading the terminal input, so it can be handed over to another process.
+    /// Returns once an in-flight poll has finished.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+        drop(self.polling.lock());
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    /// Stops the input and signal threads and waits for them, workers learn about it from
+    /// their senders.
+    pub fn shutdown(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        if let Some(signals) = self.signals.take() {
+            signals.close();
+        }
+        for thread in self.threads.drain(..) {
+            // A panic of the thread has already been reported by the hook
+            let _ = thread.join();
+        }
+    }
+}
+
+impl<T> Drop for EventLoop<T> {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+/// Posts the events of a background worker into the loop.
+pub struct EventSender<T> {
+    tx: Sender<Event<T>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl<T> EventSender<T> {
+    /// Returns false once the loop is shut down, the worker should stop then.
+    pub fn send(&self, event: T) -> bool {
+        !self.is_shut_down() && self.tx.send(Event::Worker(event)).is_ok()
+    }
+
+    pub fn is_shut_down(&self) -> bool {
+        self.shutdown.load(Ordering::SeqCst)
+    }
+}
+
+impl<T> Clone for EventSender<T> {
+    fn clone(&self) -> Self {
+        EventSender {
+            tx: self.tx.clone(),
+            shutdown: self.shutdown.clone(),
+        }
+    }
 }
 
-fn keep_emitting_events(tick_rate: Duration, tx: mpsc::Sender<Event<KeyEvent>>) {
+fn keep_emitting_events<T>(
+    tick_rate: Duration,
+    tx: Sender<Event<T>>,
+    paused: Arc<AtomicBool>,
+    polling: Arc<Mutex<()>>,
+    shutdown: Arc<AtomicBool>,
+    mut source: impl FnMut(Duration) -> io::Result<Option<Event<T>>>,
+) {
     let mut last_tick = Instant::now();
-    loop {
+    while !shutdown.load(Ordering::SeqCst) {
         let timeout = tick_rate
             .checked_sub(last_tick.elapsed())
             .unwrap_or_else(|| Duration::from_secs(0));
 
-        if event::poll(timeout).expect("poll works") {
-            if let CEvent::Key(key) = event::read().expect("can read events") {
-                tx.send(Event::Input(key)).expect("can send event");
+        let guard = polling.lock().unwrap_or_else(|it| it.into_inner());
+        if paused.load(Ordering::SeqCst) {
+            drop(guard);
+            thread::sleep(PAUSED_CHECK_INTERVAL);
+            continue;
+        }
+
+        // The terminal is gone when it can't be read anymore
+        let Ok(event) = source(timeout) else {
+            break;
+        };
+        drop(guard);
+
+        if let Some(event) = event {
+            if tx.send(event).is_err() {
+                break;
             }
         }
 
         if last_tick.elapsed() >= tick_rate {
-            if let Ok(_) = tx.send(Event::Tick) {
-                last_tick = Instant::now();
+            if tx.send(Event::Tick).is_err() {
+                break;
             }
+            last_tick = Instant::now();
         }
     }
 }
+
+/// Key or mouse event of the terminal, when one comes within the timeout.
+fn read_terminal<T>(timeout: Duration) -> io::Result<Option<Event<T>>> {
+    if !event::poll(timeout)? {
+        return Ok(None);
+    }
+    match event::read()? {
+        CEvent::Key(key) => Ok(Some(Event::Input(key))),
+        CEvent::Mouse(mouse) => Ok(Some(Event::Mouse(mouse))),
+        _ => Ok(None),
+    }
+}
+
+fn forward_signals<T>(mut signals: Signals, tx: Sender<Event<T>>) {
+    // Ends once the handle is closed on shutdown
+    for signal in signals.forever() {
+        if tx.send(Event::Signal(signal)).is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{mpsc, Arc, Mutex},
+        thread,
+        time::Duration,
+    };
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use super::{Event, EventLoop};
+
+    #[test]
+    fn deliver_worker_events_and_shut_down() {
+        // given
+        let mut event_loop = EventLoop::new();
+        event_loop.add_source(Duration::from_millis(10), |timeout| {
+            thread::sleep(timeout);
+            Ok(None)
+        });
+        let sender = event_loop.sender();
+
+        // when
+        assert!(sender.send(42));
+        let posted = loop {
+            match event_loop.next().unwrap() {
+                Event::Worker(value) => break value,
+                _ => continue,
+            }
+        };
+        event_loop.shutdown();
+
+        // then
+        assert_eq!(posted, 42);
+        assert!(sender.is_shut_down());
+        assert!(!sender.send(43));
+    }
+
+    #[test]
+    fn stop_polling_once_the_source_fails() {
+        // given
+        let mut event_loop: EventLoop<()> = EventLoop::new();
+
+        // when
+        event_loop.add_source(Duration::from_secs(60), |_| {
+            Err(io::Error::other("the terminal is gone"))
+        });
+
+        // then the thread ends without a shutdown
+        for thread in event_loop.threads.drain(..) {
+            thread.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn pause_after_the_poll_in_flight_and_resume() {
+        // given a source holding its first poll until it's released
+        let mut event_loop: EventLoop<()> = EventLoop::new();
+        let order = Arc::new(Mutex::new(vec![]));
+        let (polling_tx, polling_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let source_order = order.clone();
+        let mut polls = 0;
+        event_loop.add_source(Duration::from_secs(60), move |_| {
+            polls += 1;
+            if polls == 1 {
+                let _ = polling_tx.send(());
+                let _ = release_rx.recv();
+                source_order.lock().unwrap().push("first poll");
+                return Ok(None);
+            }
+            let key = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE);
+            Ok(Some(Event::Input(key)))
+        });
+        polling_rx.recv().unwrap();
+
+        // when
+        let release = thread::spawn(move || release_tx.send(()).unwrap());
+        event_loop.pause();
+        order.lock().unwrap().push("paused");
+        release.join().unwrap();
+        let paused = order.lock().unwrap().clone();
+        event_loop.resume();
+        let resumed = event_loop.next().unwrap();
+        event_loop.shutdown();
+
+        // then
+        assert_eq!(paused, vec!["first poll", "paused"]);
+        assert!(matches!(resumed, Event::Input(key) if key.code == KeyCode::Char('x')));
+    }
+}
@@ -0,0 +1,459 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::{errors::Error, ui::View};
+
+use super::InputAction::{self, *};
+
+/// Bindings of the `tabs` section work in all the views listed in the header.
+const TABS: &str = "tabs";
+
+/// Action name removing a default binding in the keymap file.
+const UNBOUND: &str = "none";
+
+/// Default bindings, in the order they are listed in the footer.
+const DEFAULT_BINDINGS: &[(&str, &str, InputAction)] = &[
+    (TABS, "q", Quit),
+    (TABS, "f12", Quit),
+    (TABS, "?", ShowHelp),
+    (TABS, ":", OpenCommandPrompt),
+    (TABS, "ctrl-z", Suspend),
+    (TABS, "1", ShowPods),
+    (TABS, "2", ShowDeployments),
+    (TABS, "3", ShowResources),
+    (TABS, "4", ShowEvents),
+    (TABS, "5", ShowNodes),
+    (TABS, "ctrl-t", NewWorkspace),
+    (TABS, "ctrl-w", CloseWorkspace),
+    (TABS, "]", NextWorkspace),
+    (TABS, "[", PreviousWorkspace),
+    ("pods", "down", Down),
+    ("pods", "up", Up),
+    ("pods", "tab", NextPane),
+    ("pods", "backtab", PreviousPane),
+    ("pods", "enter", FetchLogs),
+    ("pods", "pageup", PageUp),
+    ("pods", "pagedown", PageDown),
+    ("pods", "end", LogsEnd),
+    ("pods", "z", ToggleMaximisedLogs),
+    ("pods", "y", ShowManifest),
+    ("pods", "v", ShowPodEvents),
+    ("pods", "/", FilterPods),
+    ("pods", "s", SortPods),
+    ("pods", "S", ReverseSortPods),
+    ("pods", "c", PickPodColumns),
+    ("pods", "+", GrowPodList),
+    ("pods", "-", ShrinkPodList),
+    ("pods", "l", SetLabelSelector),
+    ("pods", "f", SetFieldSelector),
+    ("pods", "esc", ClearPodSelector),
+    ("deployments", "down", NextDeployment),
+    ("deployments", "up", PreviousDeployment),
+    ("deployments", "enter", ShowDeploymentPods),
+    ("deployments", "s", ScaleDeployment),
+    ("deployments", "r", RestartDeployment),
+    ("deployments", "t", RolloutStatus),
+    ("deployments", "h", RolloutHistory),
+    ("deployments", "u", UndoRollout),
+    ("deployments", "y", ShowManifest),
+    ("resources", "down", NextResource),
+    ("resources", "up", PreviousResource),
+    ("resources", "k", PickResourceKind),
+    ("resources", "enter", DescribeResource),
+    ("resources", "y", ShowManifest),
+    ("events", "down", NextEvent),
+    ("events", "up", PreviousEvent),
+    ("events", "f", ToggleEventsFilter),
+    ("nodes", "down", NextNode),
+    ("nodes", "up", PreviousNode),
+    ("nodes", "enter", ShowNodePods),
+    ("nodes", "c", CordonNode),
+    ("nodes", "u", UncordonNode),
+    ("nodes", "d", DrainNode),
+    ("nodes", "y", ShowManifest),
+    ("manifest", "down", ManifestDown),
+    ("manifest", "up", ManifestUp),
+    ("manifest", "pageup", ManifestPageUp),
+    ("manifest", "pagedown", ManifestPageDown),
+    ("manifest", "enter", ToggleFold),
+    ("manifest", "space", ToggleFold),
+    ("manifest", "/", SearchManifest),
+    ("manifest", "n", NextMatch),
+    ("manifest", "N", PreviousMatch),
+    ("manifest", "h", ToggleNoise),
+    ("manifest", "e", EditManifest),
+    ("manifest", "esc", CloseManifest),
+    ("manifest", "q", Quit),
+    ("manifest", "f12", Quit),
+    ("manifest", "?", ShowHelp),
+];
+
+/// Enabled with `vim = true`, the resource kind picker moves to `K` to make room for `k`.
+const VIM_BINDINGS: &[(&str, &str, InputAction)] = &[
+    ("pods", "j", Down),
+    ("pods", "k", Up),
+    ("pods", "g", FirstItem),
+    ("pods", "G", LastItem),
+    ("deployments", "j", NextDeployment),
+    ("deployments", "k", PreviousDeployment),
+    ("deployments", "g", FirstItem),
+    ("deployments", "G", LastItem),
+    ("resources", "j", NextResource),
+    ("resources", "k", PreviousResource),
+    ("resources", "g", FirstItem),
+    ("resources", "G", LastItem),
+    ("resources", "K", PickResourceKind),
+    ("events", "j", NextEvent),
+    ("events", "k", PreviousEvent),
+    ("events", "g", FirstItem),
+    ("events", "G", LastItem),
+    ("nodes", "j", NextNode),
+    ("nodes", "k", PreviousNode),
+    ("nodes", "g", FirstItem),
+    ("nodes", "G", LastItem),
+    ("manifest", "j", ManifestDown),
+    ("manifest", "k", ManifestUp),
+];
+
+/// `keymap` section of the config file, its sections are named after the views, ex:
+///
+/// ```toml
+/// [keymap]
+/// vim = true
+///
+/// [keymap.pods]
+/// "ctrl-l" = "fetch_logs"
+/// enter = "none"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct KeymapFile {
+    #[serde(default)]
+    pub vim: bool,
+    #[serde(flatten)]
+    pub sections: HashMap<String, HashMap<String, String>>,
+}
+
+/// Key with its modifiers, as written in the keymap file (ex: `ctrl-r`, `pagedown`, `G`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KeyChord {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyChord {
+    /// Shift is part of the character already (ex: `G`), so it is ignored for them.
+    fn from_event(event: KeyEvent) -> KeyChord {
+        let modifiers = match event.code {
+            KeyCode::Char(_) | KeyCode::BackTab => event.modifiers - KeyModifiers::SHIFT,
+            _ => event.modifiers,
+        };
+        KeyChord {
+            code: event.code,
+            modifiers,
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidKeymap(format!("unknown key {s}"));
+
+        let mut modifiers = KeyModifiers::NONE;
+        let mut key = s;
+        while let Some((modifier, rest)) = key.split_once('-').filter(|(_, it)| !it.is_empty()) {
+            modifiers |= match modifier.to_lowercase().as_str() {
+                "ctrl" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => return Err(invalid()),
+            };
+            key = rest;
+        }
+
+        let code = match key.to_lowercase().as_str() {
+            _ if key.chars().count() == 1 => KeyCode::Char(key.chars().next().ok_or_else(invalid)?),
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            name => match name.strip_prefix('f').and_then(|it| it.parse().ok()) {
+                Some(number @ 1..=12) => KeyCode::F(number),
+                _ => return Err(invalid()),
+            },
+        };
+
+        let chord = match (code, modifiers.contains(KeyModifiers::SHIFT)) {
+            (KeyCode::Char(c), true) => KeyChord {
+                code: KeyCode::Char(c.to_ascii_uppercase()),
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            (KeyCode::Tab, true) => KeyChord {
+                code: KeyCode::BackTab,
+                modifiers: modifiers - KeyModifiers::SHIFT,
+            },
+            _ => KeyChord { code, modifiers },
+        };
+        Ok(chord)
+    }
+}
+
+impl Display for KeyChord {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift-")?;
+        }
+        match self.code {
+            KeyCode::Char(' ') => write!(f, "Space"),
+            KeyCode::Char(c) => write!(f, "{c}"),
+            KeyCode::F(number) => write!(f, "F{number}"),
+            KeyCode::BackTab => write!(f, "Shift-Tab"),
+            code => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// Key chords bound to the actions, per view.
+#[derive(Clone, Debug)]
+pub struct Keymap {
+    bindings: Vec<(String, KeyChord, InputAction)>,
+}
+
+impl Keymap {
+    pub fn from_file(file: &KeymapFile, vim: bool) -> Result<Keymap, Error> {
+        let mut keymap = Keymap::defaults(vim || file.vim);
+
+        for (section, bindings) in &file.sections {
+            if section != TABS && !View::ALL.iter().any(|it| section_name(it) == *section) {
+                return Err(Error::InvalidKeymap(format!("unknown section {section}")));
+            }
+            for (chord, action) in bindings {
+                let chord = chord.parse()?;
+                match action.as_str() {
+                    UNBOUND => keymap.unbind(section, chord),
+                    action => {
+                        let action = InputAction::from_name(action).ok_or_else(|| {
+                            Error::InvalidKeymap(format!("unknown action {action}"))
+                        })?;
+                        keymap.bind(section, chord, action);
+                    }
+                }
+            }
+        }
+        Ok(keymap)
+    }
+
+    pub fn defaults(vim: bool) -> Keymap {
+        let mut keymap = Keymap { bindings: vec![] };
+        let vim_bindings = if vim { VIM_BINDINGS } else { &[] };
+
+        for (section, chord, action) in DEFAULT_BINDINGS.iter().chain(vim_bindings) {
+            let chord = chord.parse().expect("default key chords are valid");
+            keymap.bind(section, chord, *action);
+        }
+        keymap
+    }
+
+    /// Tabs bindings are the fallback for the views listed in the header.
+    pub fn action(&self, view: &View, event: KeyEvent) -> Option<InputAction> {
+        let chord = KeyChord::from_event(event);
+        let find = |section: &str| {
+            self.bindings
+                .iter()
+                .find(|(it, bound, _)| it == section && *bound == chord)
+                .map(|(_, _, action)| *action)
+        };
+
+        match find(&section_name(view)) {
+            Some(action) => Some(action),
+            None if View::TABS.contains(view) => find(TABS),
+            None => None,
+        }
+    }
+
+    /// Actions of the view with all their key chords, in the order they were bound.
+    pub fn view_bindings(&self, view: &View) -> Vec<(InputAction, Vec<KeyChord>)> {
+        self.section_bindings(&section_name(view))
+    }
+
+    /// Key chords of the action in the view, falling back to the tabs ones like `action`.
+    pub fn chords(&self, view: &View, action: InputAction) -> Vec<KeyChord> {
+        let find = |bindings: Vec<(InputAction, Vec<KeyChord>)>| {
+            bindings
+                .into_iter()
+                .find(|(it, _)| *it == action)
+                .map(|(_, chords)| chords)
+        };
+
+        match find(self.view_bindings(view)) {
+            Some(chords) => chords,
+            None if View::TABS.contains(view) => find(self.tabs_bindings()).unwrap_or_default(),
+            None => vec![],
+        }
+    }
+
+    /// Actions bound in the view, or in the tabs, by this keymap or the default one. So the ones
+    /// whose keys were removed can still be run as commands, but only where they make sense.
+    pub fn is_available(&self, view: &View, action: InputAction) -> bool {
+        !self.chords(view, action).is_empty()
+            || !Keymap::defaults(true).chords(view, action).is_empty()
+    }
+
+    pub fn tabs_bindings(&self) -> Vec<(InputAction, Vec<KeyChord>)> {
+        self.section_bindings(TABS)
+    }
+
+    fn section_bindings(&self, section: &str) -> Vec<(InputAction, Vec<KeyChord>)> {
+        let mut bindings: Vec<(InputAction, Vec<KeyChord>)> = vec![];
+        for (_, chord, action) in self.bindings.iter().filter(|(it, _, _)| it == section) {
+            match bindings.iter_mut().find(|(it, _)| it == action) {
+                Some((_, chords)) => chords.push(*chord),
+                None => bindings.push((*action, vec![*chord])),
+            }
+        }
+        bindings
+    }
+
+    /// Rebinding a chord keeps its place, so the footer order stays stable.
+    fn bind(&mut self, section: &str, chord: KeyChord, action: InputAction) {
+        match self
+            .bindings
+            .iter_mut()
+            .find(|(it, bound, _)| it == section && *bound == chord)
+        {
+            Some((_, _, bound_action)) => *bound_action = action,
+            None => self.bindings.push((section.to_owned(), chord, action)),
+        }
+    }
+
+    fn unbind(&mut self, section: &str, chord: KeyChord) {
+        self.bindings
+            .retain(|(it, bound, _)| !(it == section && *bound == chord));
+    }
+}
+
+fn section_name(view: &View) -> String {
+    view.title().to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::{input::InputAction, ui::View};
+
+    use super::{KeyChord, Keymap, KeymapFile};
+
+    #[test]
+    fn parse_key_chords() {
+        let chord: KeyChord = "ctrl-r".parse().unwrap();
+        assert_eq!(chord.to_string(), "Ctrl-r");
+
+        let chord: KeyChord = "shift-g".parse().unwrap();
+        assert_eq!(chord.to_string(), "G");
+
+        let chord: KeyChord = "pagedown".parse().unwrap();
+        assert_eq!(chord.to_string(), "PageDown");
+
+        "ctrl-".parse::<KeyChord>().unwrap_err();
+        "hyper-x".parse::<KeyChord>().unwrap_err();
+    }
+
+    #[test]
+    fn override_and_remove_default_bindings() {
+        //given
+        let file: KeymapFile = toml::from_str(
+            r#"
+vim = true
+
+[pods]
+"ctrl-l" = "fetch_logs"
+enter = "none"
+"#,
+        )
+        .unwrap();
+
+        // when
+        let keymap = Keymap::from_file(&file, false).unwrap();
+
+        //then
+        let ctrl_l = KeyEvent::new(KeyCode::Char('l'), KeyModifiers::CONTROL);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+        let shifted_g = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(
+            keymap.action(&View::Pods, ctrl_l),
+            Some(InputAction::FetchLogs)
+        );
+        assert_eq!(keymap.action(&View::Pods, enter), None);
+        assert_eq!(
+            keymap.action(&View::Pods, shifted_g),
+            Some(InputAction::LastItem)
+        );
+    }
+
+    #[test]
+    fn fall_back_to_tabs_bindings() {
+        let keymap = Keymap::defaults(false);
+        let quit = KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE);
+        let switch = KeyEvent::new(KeyCode::Char('2'), KeyModifiers::NONE);
+
+        assert_eq!(keymap.action(&View::Nodes, quit), Some(InputAction::Quit));
+        assert_eq!(keymap.action(&View::Manifest, switch), None);
+    }
+
+    #[test]
+    fn find_the_chords_of_an_action() {
+        //given
+        let file: KeymapFile = toml::from_str("[pods]\n\"ctrl-l\" = \"fetch_logs\"").unwrap();
+
+        // when
+        let keymap = Keymap::from_file(&file, false).unwrap();
+
+        //then
+        let chords: Vec<_> = keymap
+            .chords(&View::Pods, InputAction::FetchLogs)
+            .iter()
+            .map(|it| it.to_string())
+            .collect();
+        assert_eq!(chords, vec!["Enter", "Ctrl-l"]);
+        assert_eq!(
+            keymap.chords(&View::Nodes, InputAction::Quit).len(),
+            keymap.chords(&View::Pods, InputAction::Quit).len()
+        );
+        assert!(keymap
+            .chords(&View::Manifest, InputAction::FetchLogs)
+            .is_empty());
+    }
+
+    #[test]
+    fn reject_unknown_actions() {
+        let file: KeymapFile = toml::from_str("[pods]\nx = \"explode\"").unwrap();
+
+        Keymap::from_file(&file, false).unwrap_err();
+    }
+}
@@ -1,49 +1,413 @@
-use std::process::Command;
+use std::{
+    collections::HashMap,
+    path::Path,
+    process::{Command, Output},
+};
 
+use cluster_event::ClusterEvent;
+use deployment::Deployment;
 use errors::Error;
+use metrics::{PodMetrics, Resources, Usage};
+use node::{Allocatable, Node, NodeOperation};
 use pod::Pod;
+use resource_table::ResourceTable;
 
+pub mod age;
 pub mod app;
+pub mod cluster_event;
+pub mod config;
+pub mod deployment;
+pub mod editor;
 pub mod errors;
+pub mod fuzzy;
 pub mod input;
+pub mod manifest;
+pub mod metrics;
+pub mod node;
 pub mod pod;
+pub mod resource_table;
 pub mod ui;
 
-pub fn load_all_pods(namespace: &str) -> Result<Vec<Pod>, Error> {
-    let output = Command::new("/usr/local/bin/kubectl")
-        .args(["get", "pods"])
+pub fn load_all_pods(
+    namespace: &str,
+    selector: Option<&str>,
+    field_selector: Option<&str>,
+    context: Option<&str>,
+) -> Result<Vec<Pod>, Error> {
+    let mut command = kubectl(context);
+    command
+        .args(["get", "pods", "-o", "wide"])
+        .args(["-n", namespace]);
+    if let Some(selector) = selector {
+        command.args(["-l", selector]);
+    }
+    if let Some(field_selector) = field_selector {
+        command.arg(format!("--field-selector={field_selector}"));
+    }
+
+    let pods: Vec<Pod> = run(&mut command)?
+        .lines()
+        .flat_map(|it| it.parse())
+        .collect();
+
+    Ok(pods)
+}
+
+pub fn current_context() -> Result<String, Error> {
+    let mut command = kubectl(None);
+    command.args(["config", "current-context"]);
+    Ok(run(&mut command)?.trim().to_owned())
+}
+
+/// Names of the kubectl contexts, from the kubeconfig.
+pub fn load_contexts() -> Result<Vec<String>, Error> {
+    let mut command = kubectl(None);
+    command.args(["config", "get-contexts", "-o", "name"]);
+    Ok(run(&mut command)?
+        .lines()
+        .map(|it| it.trim().to_owned())
+        .collect())
+}
+
+pub fn load_namespaces(context: Option<&str>) -> Result<Vec<String>, Error> {
+    let mut command = kubectl(context);
+    command
+        .args(["get", "namespaces", "--no-headers"])
+        .args(["-o", "custom-columns=NAME:.metadata.name"]);
+    Ok(run(&mut command)?
+        .lines()
+        .map(|it| it.trim().to_owned())
+        .collect())
+}
+
+/// Logs of the pod, only the recent ones when `since` is given, ex: `10m`.
+pub fn load_logs(
+    pod_name: &str,
+    namespace: &str,
+    since: Option<&str>,
+    context: Option<&str>,
+) -> Result<Vec<String>, Error> {
+    let mut command = kubectl(context);
+    command.args(["logs", pod_name]).args(["-n", namespace]);
+    if let Some(since) = since {
+        command.arg(format!("--since={since}"));
+    }
+
+    let logs = run(&mut command)?.lines().map(|it| it.to_owned()).collect();
+    Ok(logs)
+}
+
+pub fn load_manifest(
+    kind: &str,
+    name: &str,
+    namespace: &str,
+    context: Option<&str>,
+) -> Result<String, Error> {
+    let mut command = kubectl(context);
+    command
+        .args(["get", kind, name])
         .args(["-n", namespace])
-        .output()?;
+        .args(["-o", "yaml"]);
+
+    run(&mut command)
+}
 
-    let parsed_output = String::from_utf8_lossy(&output.stdout);
+pub fn apply_manifest(path: &Path, dry_run: bool, context: Option<&str>) -> Result<String, Error> {
+    let mut command = kubectl(context);
+    command.arg("apply").arg("-f").arg(path);
+    if dry_run {
+        command.arg("--dry-run=server");
+    }
 
-    let pods: Vec<Pod> = parsed_output
+    run(&mut command)
+}
+
+/// Diff between the live resource and the file, `None` when there are no differences.
+pub fn diff_manifest(path: &Path, context: Option<&str>) -> Result<Option<String>, Error> {
+    let mut command = kubectl(context);
+    command.arg("diff").arg("-f").arg(path);
+
+    let output = command.output()?;
+    diff_output(&command, &output)
+}
+
+/// kubectl diff exits with 1 when differences were found.
+fn diff_output(command: &Command, output: &Output) -> Result<Option<String>, Error> {
+    match output.status.code() {
+        Some(0) => Ok(None),
+        Some(1) => Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned())),
+        _ => Err(failure(command, output)),
+    }
+}
+
+pub fn load_deployments(namespace: &str, context: Option<&str>) -> Result<Vec<Deployment>, Error> {
+    let mut command = kubectl(context);
+    command
+        .args(["get", "deployments"])
+        .args(["-n", namespace])
+        .args(["-o", "wide"]);
+
+    let deployments: Vec<Deployment> = run(&mut command)?
         .lines()
-        .map(|it| it.parse())
-        .filter(|it| it.is_ok())
-        .map(|it| it.unwrap())
+        .flat_map(|it| it.parse())
         .collect();
 
-    Ok(pods)
+    Ok(deployments)
 }
 
-pub fn load_namespaces() {
-    let output = Command::new("/usr/local/bin/kubectl")
-        .args(["get", "namespaces"])
-        .output()
-        .expect("Couldn't load namespaces");
+pub fn scale_deployment(
+    name: &str,
+    namespace: &str,
+    replicas: u32,
+    context: Option<&str>,
+) -> Result<String, Error> {
+    let mut command = kubectl(context);
+    command
+        .args(["scale", "deployment", name])
+        .args(["-n", namespace])
+        .arg(format!("--replicas={replicas}"));
 
-    println!("Namespaces:\n{}", String::from_utf8_lossy(&output.stdout));
+    run(&mut command)
 }
 
-pub fn load_logs(pod_name: &str, namespace: &str) -> Result<Vec<String>, Error> {
-    let output = Command::new("/usr/local/bin/kubectl")
-        .args(["logs", pod_name])
+/// Runs one of the `kubectl rollout` subcommands (restart, status, history, undo).
+pub fn rollout_deployment(
+    subcommand: &str,
+    name: &str,
+    namespace: &str,
+    args: &[String],
+    context: Option<&str>,
+) -> Result<String, Error> {
+    let mut command = kubectl(context);
+    command
+        .args(["rollout", subcommand])
+        .arg(format!("deployment/{name}"))
         .args(["-n", namespace])
-        .output()?;
+        .args(args);
 
-    let parsed_output = String::from_utf8_lossy(&output.stdout);
+    run(&mut command)
+}
 
-    let logs = parsed_output.lines().map(|it| it.to_owned()).collect(); //Avoid cloning?
-    Ok(logs)
+/// Events of the namespace, or only of the pod when its name is given.
+pub fn load_events(
+    namespace: &str,
+    pod_name: Option<&str>,
+    context: Option<&str>,
+) -> Result<Vec<ClusterEvent>, Error> {
+    let mut command = kubectl(context);
+    command
+        .args(["get", "events"])
+        .args(["-n", namespace])
+        .arg(format!(
+            "-o=custom-columns={}",
+            cluster_event::EVENT_COLUMNS
+        ))
+        .arg("--no-headers");
+    if let Some(pod_name) = pod_name {
+        command.arg(format!(
+            "--field-selector=involvedObject.kind=Pod,involvedObject.name={pod_name}"
+        ));
+    }
+
+    let mut events: Vec<ClusterEvent> = run(&mut command)?
+        .lines()
+        .flat_map(|it| it.parse())
+        .collect();
+    cluster_event::sort_by_last_seen(&mut events);
+
+    Ok(events)
+}
+
+pub fn load_nodes(context: Option<&str>) -> Result<Vec<Node>, Error> {
+    let mut command = kubectl(context);
+    command.args(["get", "nodes", "--no-headers"]);
+    let mut nodes: Vec<Node> = run(&mut command)?
+        .lines()
+        .flat_map(|it| it.parse())
+        .collect();
+
+    let mut command = kubectl(context);
+    command
+        .args(["get", "nodes", "--no-headers"])
+        .arg(format!("-o=custom-columns={}", node::ALLOCATABLE_COLUMNS));
+    let allocatable: Vec<Allocatable> = run(&mut command)?
+        .lines()
+        .flat_map(|it| it.parse())
+        .collect();
+
+    let mut command = kubectl(context);
+    command
+        .args(["get", "pods", "--all-namespaces", "--no-headers"])
+        .arg("-o=custom-columns=NODE:.spec.nodeName");
+    let mut pods_per_node = HashMap::new();
+    for node_name in run(&mut command)?.lines() {
+        *pods_per_node
+            .entry(node_name.trim().to_owned())
+            .or_insert(0) += 1;
+    }
+
+    for node in nodes.iter_mut() {
+        if let Some(allocatable) = allocatable.iter().find(|it| it.name == node.name) {
+            node.set_allocatable(allocatable);
+        }
+        node.set_pods_number(&pods_per_node);
+    }
+    Ok(nodes)
+}
+
+/// Usage comes from the metrics API, pods keep their requests and limits when it isn't available.
+pub fn load_pod_metrics(namespace: &str, context: Option<&str>) -> Result<Vec<PodMetrics>, Error> {
+    let mut command = kubectl(context);
+    command
+        .args(["get", "pods", "--no-headers", "-n", namespace])
+        .arg(format!("-o=custom-columns={}", metrics::RESOURCES_COLUMNS));
+    let resources: Vec<Resources> = run(&mut command)?
+        .lines()
+        .flat_map(|it| it.parse())
+        .collect();
+
+    let mut command = kubectl(context);
+    command.args(["top", "pods", "--no-headers", "-n", namespace]);
+    let usages: Vec<Usage> = run(&mut command)
+        .map(|it| it.lines().flat_map(|it| it.parse()).collect())
+        .unwrap_or_default();
+
+    Ok(resources
+        .iter()
+        .map(|resources| {
+            let usage = usages.iter().find(|it| it.name == resources.name);
+            PodMetrics::new(usage, resources)
+        })
+        .collect())
+}
+
+pub fn operate_on_node(
+    operation: NodeOperation,
+    name: &str,
+    context: Option<&str>,
+) -> Result<String, Error> {
+    let mut command = kubectl(context);
+    command
+        .args([operation.command(), name])
+        .args(operation.args());
+
+    run(&mut command)
+}
+
+/// Names of all resource kinds which can be listed, including custom resources.
+pub fn load_resource_kinds(context: Option<&str>) -> Result<Vec<String>, Error> {
+    let mut command = kubectl(context);
+    command.args(["api-resources", "--verbs=list", "-o", "name"]);
+
+    let kinds = run(&mut command)?
+        .lines()
+        .map(|it| it.trim().to_owned())
+        .filter(|it| !it.is_empty())
+        .collect();
+    Ok(kinds)
+}
+
+pub fn load_resources(
+    kind: &str,
+    namespace: &str,
+    context: Option<&str>,
+) -> Result<ResourceTable, Error> {
+    let mut command = kubectl(context);
+    command.args(["get", kind]).args(["-n", namespace]);
+
+    Ok(ResourceTable::parse(kind, &run(&mut command)?))
+}
+
+pub fn delete_resource(
+    kind: &str,
+    name: &str,
+    namespace: &str,
+    context: Option<&str>,
+) -> Result<String, Error> {
+    let mut command = kubectl(context);
+    command.args(["delete", kind, name]).args(["-n", namespace]);
+
+    run(&mut command)
+}
+
+pub fn describe_resource(
+    kind: &str,
+    name: &str,
+    namespace: &str,
+    context: Option<&str>,
+) -> Result<String, Error> {
+    let mut command = kubectl(context);
+    command
+        .args(["describe", kind, name])
+        .args(["-n", namespace]);
+
+    run(&mut command)
+}
+
+/// kubectl using the given context, or the current one.
+fn kubectl(context: Option<&str>) -> Command {
+    let mut command = Command::new("kubectl");
+    if let Some(context) = context {
+        command.arg(format!("--context={context}"));
+    }
+    command
+}
+
+/// Output of kubectl, or its error when it doesn't succeed.
+fn run(command: &mut Command) -> Result<String, Error> {
+    let output = command.output()?;
+    if !output.status.success() {
+        return Err(failure(command, &output));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn failure(command: &Command, output: &Output) -> Error {
+    let args: Vec<_> = command.get_args().map(|it| it.to_string_lossy()).collect();
+    Error::KubectlFailed {
+        command: args.join(" "),
+        code: output.status.code(),
+        stderr: String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+    use std::process::ExitStatus;
+
+    use super::*;
+
+    fn diff_exiting_with(code: i32) -> Result<Option<String>, Error> {
+        let mut command = kubectl(None);
+        command.args(["diff", "-f", "pod.yaml"]);
+        let output = Output {
+            // Wait status, the exit code is in the second byte
+            status: ExitStatus::from_raw(code << 8),
+            stdout: b"-replicas: 1\n+replicas: 2\n".to_vec(),
+            stderr: b"error: the server doesn't have a resource type\n".to_vec(),
+        };
+        diff_output(&command, &output)
+    }
+
+    #[test]
+    fn map_the_exit_codes_of_kubectl_diff() {
+        assert!(diff_exiting_with(0).unwrap().is_none());
+        assert_eq!(
+            diff_exiting_with(1).unwrap().as_deref(),
+            Some("-replicas: 1\n+replicas: 2\n")
+        );
+        match diff_exiting_with(2) {
+            Err(Error::KubectlFailed {
+                command,
+                code,
+                stderr,
+            }) => {
+                assert_eq!(command, "diff -f pod.yaml");
+                assert_eq!(code, Some(2));
+                assert_eq!(stderr, "error: the server doesn't have a resource type");
+            }
+            other => panic!("unexpected result {other:?}"),
+        }
+    }
 }
@@ -1,14 +1,79 @@
-use kuber_rs::{self, app::App, ui::UI};
+use std::process;
+
+use kuber_rs::{
+    self,
+    app::App,
+    config::Config,
+    current_context,
+    errors::Error,
+    input::keymap::Keymap,
+    pod::PodColumn,
+    ui::{theme::Theme, UI},
+};
 
 fn main() {
-    println!("Started");
     let args = Args::parse();
+    let print_config = args.print_config;
+
+    let (config, keymap, theme) = load_config(&args).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        process::exit(1);
+    });
+    let startup = startup_config(&config, args);
+    if print_config {
+        print!("{}", startup.to_toml());
+        return;
+    }
+
+    println!("Started");
+    println!("namespace: {:?}", startup.namespace);
+
+    let mut app = App::new(
+        startup.namespace,
+        None,
+        startup.selector,
+        startup.field_selector,
+    );
+    let mut ui = UI::new(&mut app, config, keymap, theme);
+    if let Err(err) = ui.start() {
+        eprintln!("{err}");
+        process::exit(1);
+    }
+}
+
+/// Config file with the flags which apply to every workspace, the defaults of the kubectl
+/// contexts are left to the workspaces on them.
+fn load_config(args: &Args) -> Result<(Config, Keymap, Theme), Error> {
+    let mut config = Config::load()?;
+    config.current_context = current_context().ok();
+
+    if let Some(columns) = &args.columns {
+        config.columns = columns.clone();
+    }
+    if let Some(theme) = &args.theme {
+        config.theme = theme.clone();
+    }
+    config.keymap.vim |= args.vim;
 
-    println!("namespace: {:?}", args.namespace);
+    let keymap = Keymap::from_file(&config.keymap, config.keymap.vim)?;
+    let theme = config.theme()?;
+    Ok((config, keymap, theme))
+}
 
-    let mut app = App::new(args.namespace);
-    let mut ui = UI::new(&mut app);
-    ui.start().expect("should start");
+/// Config of the first workspace: the defaults for the current kubectl context, then the
+/// namespace and selector flags, which apply only to it.
+fn startup_config(config: &Config, args: Args) -> Config {
+    let mut startup = config.for_context(None);
+    if let Some(namespace) = args.namespace {
+        startup.namespace = namespace;
+    }
+    if args.selector.is_some() {
+        startup.selector = args.selector;
+    }
+    if args.field_selector.is_some() {
+        startup.field_selector = args.field_selector;
+    }
+    startup
 }
 
 use clap::Parser;
@@ -17,7 +82,70 @@ use clap::Parser;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// Namespace to be used
-    #[arg(short, long, default_value = "default")]
-    namespace: String,
+    /// Namespace to be used [default: default]
+    #[arg(short, long)]
+    namespace: Option<String>,
+
+    /// Label selector for the pods (ex: app=api,tier!=cache)
+    #[arg(short = 'l', long)]
+    selector: Option<String>,
+
+    /// Field selector for the pods (ex: status.phase=Running)
+    #[arg(long)]
+    field_selector: Option<String>,
+
+    /// Columns of the pods table (name, ready, status, restarts, age, node, ip)
+    #[arg(long, value_delimiter = ',')]
+    columns: Option<Vec<PodColumn>>,
+
+    /// Colour theme (dark, light, high-contrast, monochrome or one from the config)
+    #[arg(long)]
+    theme: Option<String>,
+
+    /// Vim-style keys (j/k/g/G) on top of the keymap
+    #[arg(long)]
+    vim: bool,
+
+    /// Print the config merged with the flags and exit
+    #[arg(long)]
+    print_config: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_the_selector_flags() {
+        // when
+        let args = Args::try_parse_from([
+            "kuber",
+            "-l",
+            "app=api,tier!=cache",
+            "--field-selector",
+            "status.phase=Running",
+        ])
+        .unwrap();
+
+        // then
+        assert_eq!(args.selector.as_deref(), Some("app=api,tier!=cache"));
+        assert_eq!(args.field_selector.as_deref(), Some("status.phase=Running"));
+    }
+
+    #[test]
+    fn apply_the_selector_flags_to_the_first_workspace_only() {
+        // given
+        let mut config = Config::parse("[contexts.A]\nselector = \"tier=backend\"").unwrap();
+        config.current_context = Some("A".to_owned());
+        let args = Args::try_parse_from(["kuber", "-n", "dev", "-l", "app=api"]).unwrap();
+
+        // when
+        let startup = startup_config(&config, args);
+
+        // then
+        assert_eq!(startup.namespace, "dev");
+        assert_eq!(startup.selector.as_deref(), Some("app=api"));
+        assert_eq!(config.for_context(Some("B")).selector, None);
+        assert_eq!(config.for_context(Some("B")).namespace, "default");
+    }
 }
@@ -0,0 +1,176 @@
+use std::collections::HashSet;
+
+/// Sections which are rarely interesting when reading a manifest and can be hidden.
+const NOISE_SECTIONS: [(usize, &str); 2] = [(0, "status:"), (2, "managedFields:")];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Manifest {
+    kind: String,
+    name: String,
+    lines: Vec<String>,
+}
+
+impl Manifest {
+    pub fn new(kind: &str, name: &str, yaml: &str) -> Manifest {
+        Manifest {
+            kind: kind.to_owned(),
+            name: name.to_owned(),
+            lines: yaml.lines().map(|it| it.to_owned()).collect(),
+        }
+    }
+
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn lines(&self) -> &Vec<String> {
+        &self.lines
+    }
+
+    pub fn yaml(&self) -> String {
+        let mut yaml = self.lines.join("\n");
+        yaml.push('\n');
+        yaml
+    }
+
+    pub fn line(&self, index: usize) -> &str {
+        &self.lines[index]
+    }
+
+    pub fn is_foldable(&self, index: usize) -> bool {
+        self.block_end(index) > index + 1
+    }
+
+    /// Indexes of the lines left after applying folds and optionally hiding noisy sections.
+    pub fn visible_lines(&self, folded: &HashSet<usize>, hide_noise: bool) -> Vec<usize> {
+        let mut visible = vec![];
+        let mut index = 0;
+
+        while index < self.lines.len() {
+            if hide_noise && self.is_noise(index) {
+                index = self.block_end(index);
+                continue;
+            }
+
+            visible.push(index);
+            index = if folded.contains(&index) {
+                self.block_end(index)
+            } else {
+                index + 1
+            };
+        }
+
+        visible
+    }
+
+    pub fn matches(&self, index: usize, query: &str) -> bool {
+        !query.is_empty()
+            && self.lines[index]
+                .to_lowercase()
+                .contains(&query.to_lowercase())
+    }
+
+    /// Exclusive end of the block started by the line, the line itself for a scalar entry.
+    fn block_end(&self, index: usize) -> usize {
+        let indent = indentation(&self.lines[index]);
+        let is_mapping_key = self.lines[index].trim_end().ends_with(':')
+            && !self.lines[index].trim_start().starts_with("- ");
+
+        let mut end = index + 1;
+        while end < self.lines.len() {
+            let line = &self.lines[end];
+            let nested = indentation(line) > indent
+                || (is_mapping_key
+                    && indentation(line) == indent
+                    && line.trim_start().starts_with("- "));
+
+            if !nested && !line.trim().is_empty() {
+                break;
+            }
+            end += 1;
+        }
+        end
+    }
+
+    fn is_noise(&self, index: usize) -> bool {
+        let line = &self.lines[index];
+        NOISE_SECTIONS
+            .iter()
+            .any(|(indent, key)| indentation(line) == *indent && line.trim() == *key)
+    }
+}
+
+fn indentation(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::Manifest;
+
+    const YAML: &str = "apiVersion: v1
+kind: Pod
+metadata:
+  managedFields:
+  - apiVersion: v1
+    manager: kubectl
+  name: api
+spec:
+  containers:
+  - image: api:1.0
+    name: api
+status:
+  phase: Running";
+
+    #[test]
+    fn show_all_lines_by_default() {
+        //given
+        let manifest = Manifest::new("pod", "api", YAML);
+
+        //when
+        let visible = manifest.visible_lines(&HashSet::new(), false);
+
+        //then
+        assert_eq!(visible, (0..13).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn fold_mapping_with_list_on_the_same_indentation() {
+        //given
+        let manifest = Manifest::new("pod", "api", YAML);
+        let folded = HashSet::from([8]);
+
+        //when
+        let visible = manifest.visible_lines(&folded, false);
+
+        //then
+        assert_eq!(visible, vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 11, 12]);
+    }
+
+    #[test]
+    fn hide_managed_fields_and_status() {
+        //given
+        let manifest = Manifest::new("pod", "api", YAML);
+
+        //when
+        let visible = manifest.visible_lines(&HashSet::new(), true);
+
+        //then
+        assert_eq!(visible, vec![0, 1, 2, 6, 7, 8, 9, 10]);
+    }
+
+    #[test]
+    fn scalar_entries_are_not_foldable() {
+        let manifest = Manifest::new("pod", "api", YAML);
+
+        assert!(!manifest.is_foldable(0));
+        assert!(manifest.is_foldable(4));
+        assert!(!manifest.is_foldable(12));
+    }
+}
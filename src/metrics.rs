@@ -0,0 +1,223 @@
+use std::str::FromStr;
+
+use crate::errors::Error;
+
+/// Columns requested from kubectl for the summed up requests and limits of the pod containers.
+pub const RESOURCES_COLUMNS: &str = "NAME:.metadata.name,CPU_REQUEST:.spec.containers[*].resources.requests.cpu,CPU_LIMIT:.spec.containers[*].resources.limits.cpu,MEMORY_REQUEST:.spec.containers[*].resources.requests.memory,MEMORY_LIMIT:.spec.containers[*].resources.limits.memory";
+
+const NONE: &str = "<none>";
+
+/// Current usage of the pod, cpu in millicores and memory in bytes.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PodMetrics {
+    pub name: String,
+    pub cpu: Option<u64>,
+    pub memory: Option<u64>,
+    pub cpu_request: Option<u64>,
+    pub cpu_limit: Option<u64>,
+    pub memory_request: Option<u64>,
+    pub memory_limit: Option<u64>,
+}
+
+impl PodMetrics {
+    pub fn new(usage: Option<&Usage>, resources: &Resources) -> PodMetrics {
+        PodMetrics {
+            name: resources.name.clone(),
+            cpu: usage.map(|it| it.cpu),
+            memory: usage.map(|it| it.memory),
+            cpu_request: resources.cpu_request,
+            cpu_limit: resources.cpu_limit,
+            memory_request: resources.memory_request,
+            memory_limit: resources.memory_limit,
+        }
+    }
+}
+
+/// Line of `kubectl top pods`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Usage {
+    pub name: String,
+    pub cpu: u64,
+    pub memory: u64,
+}
+
+impl FromStr for Usage {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split_whitespace().collect();
+
+        if parts.len() != 3 {
+            return Err(Error::ParseOutputError);
+        };
+
+        Ok(Usage {
+            name: parts[0].to_string(),
+            cpu: parse_cpu(parts[1]).ok_or(Error::ParseOutputError)?,
+            memory: parse_memory(parts[2]).ok_or(Error::ParseOutputError)?,
+        })
+    }
+}
+
+/// Line of `kubectl get pods` with the `RESOURCES_COLUMNS`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Resources {
+    pub name: String,
+    pub cpu_request: Option<u64>,
+    pub cpu_limit: Option<u64>,
+    pub memory_request: Option<u64>,
+    pub memory_limit: Option<u64>,
+}
+
+impl FromStr for Resources {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split_whitespace().collect();
+
+        if parts.len() != 5 {
+            return Err(Error::ParseOutputError);
+        };
+
+        Ok(Resources {
+            name: parts[0].to_string(),
+            cpu_request: sum_containers(parts[1], parse_cpu),
+            cpu_limit: sum_containers(parts[2], parse_cpu),
+            memory_request: sum_containers(parts[3], parse_memory),
+            memory_limit: sum_containers(parts[4], parse_memory),
+        })
+    }
+}
+
+fn sum_containers(values: &str, parse: fn(&str) -> Option<u64>) -> Option<u64> {
+    match values {
+        NONE => None,
+        values => values.split(',').map(parse).sum(),
+    }
+}
+
+/// Parses a cpu quantity (ex: `250m`, `1.5`, `1200000n`) into millicores.
+pub fn parse_cpu(quantity: &str) -> Option<u64> {
+    let (number, millis_per_unit) = match quantity {
+        q if q.ends_with('n') => (&q[..q.len() - 1], 0.000_001),
+        q if q.ends_with('u') => (&q[..q.len() - 1], 0.001),
+        q if q.ends_with('m') => (&q[..q.len() - 1], 1.0),
+        q => (q, 1000.0),
+    };
+    number
+        .parse::<f64>()
+        .ok()
+        .map(|it| (it * millis_per_unit).round() as u64)
+}
+
+/// Parses a memory quantity (ex: `128Mi`, `1G`, `1024`) into bytes.
+pub fn parse_memory(quantity: &str) -> Option<u64> {
+    const SUFFIXES: [(&str, u64); 12] = [
+        ("Ki", 1 << 10),
+        ("Mi", 1 << 20),
+        ("Gi", 1 << 30),
+        ("Ti", 1 << 40),
+        ("Pi", 1 << 50),
+        ("Ei", 1 << 60),
+        ("k", 1_000),
+        ("M", 1_000_000),
+        ("G", 1_000_000_000),
+        ("T", 1_000_000_000_000),
+        ("P", 1_000_000_000_000_000),
+        ("E", 1_000_000_000_000_000_000),
+    ];
+
+    let (number, multiplier) = SUFFIXES
+        .iter()
+        .find_map(|(suffix, multiplier)| {
+            quantity
+                .strip_suffix(suffix)
+                .map(|number| (number, *multiplier))
+        })
+        .unwrap_or((quantity, 1));
+    number
+        .parse::<f64>()
+        .ok()
+        .map(|it| (it * multiplier as f64).round() as u64)
+}
+
+pub fn format_cpu(millicores: u64) -> String {
+    format!("{millicores}m")
+}
+
+pub fn format_memory(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{:.1}Gi", b as f64 / (1u64 << 30) as f64),
+        b if b >= 1 << 20 => format!("{}Mi", b >> 20),
+        b if b >= 1 << 10 => format!("{}Ki", b >> 10),
+        b => format!("{b}"),
+    }
+}
+
+/// Usage as a percentage of the request or limit, if both are known.
+pub fn percent(usage: Option<u64>, of: Option<u64>) -> Option<u64> {
+    match (usage, of) {
+        (Some(usage), Some(of)) if of > 0 => Some(usage * 100 / of),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_memory, parse_cpu, parse_memory, Resources, Usage};
+
+    #[test]
+    fn parse_cpu_quantities() {
+        assert_eq!(parse_cpu("250m"), Some(250));
+        assert_eq!(parse_cpu("1.5"), Some(1500));
+        assert_eq!(parse_cpu("2"), Some(2000));
+        assert_eq!(parse_cpu("1200000n"), Some(1));
+        assert_eq!(parse_cpu("abc"), None);
+    }
+
+    #[test]
+    fn parse_memory_quantities() {
+        assert_eq!(parse_memory("128Mi"), Some(128 * 1024 * 1024));
+        assert_eq!(parse_memory("1G"), Some(1_000_000_000));
+        assert_eq!(parse_memory("512Ki"), Some(512 * 1024));
+        assert_eq!(parse_memory("1024"), Some(1024));
+    }
+
+    #[test]
+    fn format_memory_with_binary_suffixes() {
+        assert_eq!(format_memory(128 * 1024 * 1024), "128Mi");
+        assert_eq!(format_memory(3 * 1024 * 1024 * 1024 / 2), "1.5Gi");
+    }
+
+    #[test]
+    fn parse_top_line() {
+        //given
+        let top_line = "api-7dd394858c-sxctv   12m          87Mi";
+        let expected = Usage {
+            name: "api-7dd394858c-sxctv".to_string(),
+            cpu: 12,
+            memory: 87 * 1024 * 1024,
+        };
+
+        // when
+        let actual: Usage = top_line.parse().unwrap();
+
+        //then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn sum_resources_of_all_containers() {
+        //given
+        let resources_line = "api-7dd394858c-sxctv   100m,50m   <none>   128Mi,64Mi   256Mi";
+
+        // when
+        let actual: Resources = resources_line.parse().unwrap();
+
+        //then
+        assert_eq!(actual.cpu_request, Some(150));
+        assert_eq!(actual.cpu_limit, None);
+        assert_eq!(actual.memory_request, Some(192 * 1024 * 1024));
+        assert_eq!(actual.memory_limit, Some(256 * 1024 * 1024));
+    }
+}
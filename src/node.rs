@@ -0,0 +1,187 @@
+use std::{collections::HashMap, str::FromStr};
+
+use crate::errors::Error;
+
+/// Columns requested from kubectl for the allocatable resources and taints of the nodes.
+pub const ALLOCATABLE_COLUMNS: &str = "NAME:.metadata.name,CPU:.status.allocatable.cpu,MEMORY:.status.allocatable.memory,TAINT_KEYS:.spec.taints[*].key,TAINT_EFFECTS:.spec.taints[*].effect";
+
+const NONE: &str = "<none>";
+
+/// Node as listed by `kubectl get nodes`, completed with its allocatable resources.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Node {
+    pub name: String,
+    pub status: String,
+    pub roles: String,
+    pub age: String,
+    pub version: String,
+    pub cpu: String,
+    pub memory: String,
+    pub taints: Vec<String>,
+    pub pods: usize,
+}
+
+impl Node {
+    /// Fills in the details coming from the `ALLOCATABLE_COLUMNS` line of the node.
+    pub fn set_allocatable(&mut self, allocatable: &Allocatable) {
+        self.cpu = allocatable.cpu.clone();
+        self.memory = allocatable.memory.clone();
+        self.taints = allocatable.taints.clone();
+    }
+
+    pub fn set_pods_number(&mut self, pods_per_node: &HashMap<String, usize>) {
+        self.pods = pods_per_node.get(&self.name).copied().unwrap_or(0);
+    }
+}
+
+impl FromStr for Node {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split_whitespace().collect();
+
+        if parts.len() != 5 || parts[0] == "NAME" {
+            return Err(Error::ParseOutputError);
+        };
+
+        Ok(Node {
+            name: parts[0].to_string(),
+            status: parts[1].to_string(),
+            roles: parts[2].to_string(),
+            age: parts[3].to_string(),
+            version: parts[4].to_string(),
+            cpu: String::new(),
+            memory: String::new(),
+            taints: vec![],
+            pods: 0,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Allocatable {
+    pub name: String,
+    pub cpu: String,
+    pub memory: String,
+    pub taints: Vec<String>,
+}
+
+impl FromStr for Allocatable {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split_whitespace().collect();
+
+        if parts.len() != 5 || parts[0] == "NAME" {
+            return Err(Error::ParseOutputError);
+        };
+
+        let taints = match (parts[3], parts[4]) {
+            (NONE, _) | (_, NONE) => vec![],
+            (keys, effects) => keys
+                .split(',')
+                .zip(effects.split(','))
+                .map(|(key, effect)| format!("{key}:{effect}"))
+                .collect(),
+        };
+
+        Ok(Allocatable {
+            name: parts[0].to_string(),
+            cpu: parts[1].to_string(),
+            memory: parts[2].to_string(),
+            taints,
+        })
+    }
+}
+
+/// Operations changing whether pods can be scheduled on the node.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NodeOperation {
+    Cordon,
+    Uncordon,
+    Drain,
+}
+
+impl NodeOperation {
+    pub fn command(&self) -> &'static str {
+        match self {
+            NodeOperation::Cordon => "cordon",
+            NodeOperation::Uncordon => "uncordon",
+            NodeOperation::Drain => "drain",
+        }
+    }
+
+    pub fn args(&self) -> &'static [&'static str] {
+        match self {
+            NodeOperation::Drain => &[
+                "--ignore-daemonsets",
+                "--delete-emptydir-data",
+                "--timeout=120s",
+            ],
+            _ => &[],
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            NodeOperation::Cordon => "New pods won't be scheduled on the node.",
+            NodeOperation::Uncordon => "New pods will be scheduled on the node again.",
+            NodeOperation::Drain => {
+                "The node will be cordoned and all its pods, except daemon sets, evicted."
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::errors::Error;
+
+    use super::{Allocatable, Node};
+
+    #[test]
+    fn parse_node_info() {
+        //given
+        let node_info = "node-1   Ready,SchedulingDisabled   control-plane   120d   v1.25.3";
+
+        // when
+        let actual: Node = node_info.parse().unwrap();
+
+        //then
+        assert_eq!(actual.name, "node-1");
+        assert_eq!(actual.status, "Ready,SchedulingDisabled");
+        assert_eq!(actual.roles, "control-plane");
+        assert_eq!(actual.version, "v1.25.3");
+    }
+
+    #[test]
+    fn parse_allocatable_with_taints() {
+        //given
+        let allocatable_info = "node-1   3800m   7Gi   node-role.kubernetes.io/control-plane,dedicated   NoSchedule,NoExecute";
+        let expected = Allocatable {
+            name: "node-1".to_string(),
+            cpu: "3800m".to_string(),
+            memory: "7Gi".to_string(),
+            taints: vec![
+                "node-role.kubernetes.io/control-plane:NoSchedule".to_string(),
+                "dedicated:NoExecute".to_string(),
+            ],
+        };
+
+        // when
+        let actual: Allocatable = allocatable_info.parse().unwrap();
+
+        //then
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn should_not_parse_headers() {
+        //given
+        let header = "NAME     STATUS   ROLES           AGE    VERSION";
+        //when
+        let result: Result<Node, Error> = header.parse();
+        // then expect error
+        result.unwrap_err();
+    }
+}
@@ -1,14 +1,56 @@
-use std::str::FromStr;
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+    time::Duration,
+};
 
-use crate::errors::Error;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    age::{format_age, parse_age},
+    errors::Error,
+};
+
+/// Shown in place of an age kubectl couldn't compute.
+const UNKNOWN_AGE: &str = "<unknown>";
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Pod {
     pub name: String,
-    pub ready: String,
+    pub ready: Readiness,
     pub status: String,
-    pub restarts: String,
-    pub age: String,
+    pub restarts: u32,
+    pub last_restart: Option<Duration>,
+    /// `None` when kubectl doesn't know it, ex: `<invalid>` under clock skew.
+    pub age: Option<Duration>,
+    pub ip: String,
+    pub node: String,
+}
+
+impl Pod {
+    pub fn health(&self) -> PodHealth {
+        PodHealth::of_status(&self.status)
+    }
+
+    /// Some containers aren't ready, finished pods don't count as their containers are stopped.
+    pub fn is_not_ready(&self) -> bool {
+        !self.ready.is_fully_ready() && !matches!(self.status.as_str(), "Completed" | "Succeeded")
+    }
+
+    /// Restarts as printed by kubectl, ex: `1 (8d ago)`.
+    pub fn restarts_description(&self) -> String {
+        match self.last_restart {
+            Some(last_restart) => format!("{} ({} ago)", self.restarts, format_age(last_restart)),
+            None => self.restarts.to_string(),
+        }
+    }
+
+    pub fn age_description(&self) -> String {
+        self.age
+            .map(format_age)
+            .unwrap_or_else(|| UNKNOWN_AGE.to_owned())
+    }
 }
 
 impl FromStr for Pod {
@@ -17,48 +59,205 @@ impl FromStr for Pod {
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let parts: Vec<_> = s.split_whitespace().collect();
 
-        if parts.len() != 5 && parts.len() != 7 {
-            println!("{:?}", parts);
-            return Err(Error::ParseOutputError);
+        // `-o wide` adds the ip, node, nominated node and readiness gates
+        let (parts, ip, node) = match parts.len() {
+            9 | 11 => {
+                let wide = parts.len() - 4;
+                (&parts[..wide], parts[wide], parts[wide + 1])
+            }
+            5 | 7 => (&parts[..], "", ""),
+            _ => return Err(Error::ParseOutputError),
         };
 
-        // Should be a number of restarts
-        if parts[3].parse::<u16>().is_err() {
-            return Err(Error::ParseOutputError);
-        }
-
-        let restarts;
-        let age;
-
-        if parts.len() == 7 {
+        let restarts = parts[3].parse().map_err(|_| Error::ParseOutputError)?;
+        let (last_restart, age) = match parts.len() {
             // ex: 1 (8d ago)
-            restarts = parts[3..=5].join(" ");
-            age = parts[6].to_string();
-        } else {
+            7 => (parse_age(parts[4].trim_start_matches('(')), parts[6]),
             // ex: 0
-            restarts = parts[3].to_string();
-            age = parts[4].to_string();
-        }
-
-        let name = parts[0].to_string();
-        let ready = parts[1].to_string();
-        let status = parts[2].to_string();
+            _ => (None, parts[4]),
+        };
 
         Ok(Pod {
-            name,
-            ready,
-            status,
+            name: parts[0].to_string(),
+            ready: parts[1].parse()?,
+            status: parts[2].to_string(),
             restarts,
-            age,
+            last_restart,
+            age: parse_age(age),
+            ip: ip.to_string(),
+            node: node.to_string(),
         })
     }
 }
 
+/// Rough state of the pod derived from the status printed by kubectl.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PodHealth {
+    Failing,
+    Pending,
+    Terminating,
+    Healthy,
+    Unknown,
+}
+
+impl PodHealth {
+    /// Init containers are reported with a prefix, ex: `Init:CrashLoopBackOff`, `Init:0/2`.
+    pub fn of_status(status: &str) -> PodHealth {
+        const FAILING: [&str; 11] = [
+            "CrashLoopBackOff",
+            "Error",
+            "OOMKilled",
+            "ImagePullBackOff",
+            "ErrImagePull",
+            "InvalidImageName",
+            "CreateContainerConfigError",
+            "CreateContainerError",
+            "RunContainerError",
+            "Evicted",
+            "Failed",
+        ];
+        let reason = status.trim_start_matches("Init:");
+
+        match status {
+            _ if FAILING.contains(&reason) => PodHealth::Failing,
+            "Running" | "Completed" | "Succeeded" => PodHealth::Healthy,
+            "Terminating" => PodHealth::Terminating,
+            "Pending" | "ContainerCreating" | "PodInitializing" => PodHealth::Pending,
+            status if status.starts_with("Init:") => PodHealth::Pending,
+            _ => PodHealth::Unknown,
+        }
+    }
+}
+
+/// Number of pods per status, the failing ones first.
+pub fn count_by_status(pods: &[Pod]) -> Vec<(&str, usize)> {
+    let mut counts: Vec<(&str, usize)> = vec![];
+    for pod in pods {
+        match counts.iter_mut().find(|(status, _)| *status == pod.status) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((&pod.status, 1)),
+        }
+    }
+    counts.sort_by_key(|(status, _)| (PodHealth::of_status(status), *status));
+    counts
+}
+
+/// Number of ready containers out of all of them, ex: `1/2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Readiness {
+    pub ready: u32,
+    pub total: u32,
+}
+
+impl Readiness {
+    pub fn is_fully_ready(&self) -> bool {
+        self.ready == self.total
+    }
+}
+
+impl Display for Readiness {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.ready, self.total)
+    }
+}
+
+impl FromStr for Readiness {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (ready, total) = s.split_once('/').ok_or(Error::ParseOutputError)?;
+        Ok(Readiness {
+            ready: ready.parse().map_err(|_| Error::ParseOutputError)?,
+            total: total.parse().map_err(|_| Error::ParseOutputError)?,
+        })
+    }
+}
+
+/// Columns of the pods table, each one can be hidden and used for sorting.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PodColumn {
+    Name,
+    Ready,
+    Status,
+    Restarts,
+    Age,
+    Node,
+    Ip,
+}
+
+impl PodColumn {
+    pub const ALL: [PodColumn; 7] = [
+        PodColumn::Name,
+        PodColumn::Ready,
+        PodColumn::Status,
+        PodColumn::Restarts,
+        PodColumn::Age,
+        PodColumn::Node,
+        PodColumn::Ip,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            PodColumn::Name => "Name",
+            PodColumn::Ready => "Ready",
+            PodColumn::Status => "Status",
+            PodColumn::Restarts => "Restarts",
+            PodColumn::Age => "Age",
+            PodColumn::Node => "Node",
+            PodColumn::Ip => "IP",
+        }
+    }
+
+    pub fn value(&self, pod: &Pod) -> String {
+        match self {
+            PodColumn::Name => pod.name.clone(),
+            PodColumn::Ready => pod.ready.to_string(),
+            PodColumn::Status => pod.status.clone(),
+            PodColumn::Restarts => pod.restarts_description(),
+            PodColumn::Age => pod.age_description(),
+            PodColumn::Node => pod.node.clone(),
+            PodColumn::Ip => pod.ip.clone(),
+        }
+    }
+
+    /// Typed columns are compared by their values, the youngest pods go first and the ones of
+    /// unknown age last.
+    pub fn compare(&self, a: &Pod, b: &Pod) -> Ordering {
+        match self {
+            PodColumn::Ready => a.ready.cmp(&b.ready),
+            PodColumn::Restarts => a.restarts.cmp(&b.restarts),
+            PodColumn::Age => a
+                .age
+                .is_none()
+                .cmp(&b.age.is_none())
+                .then(a.age.cmp(&b.age)),
+            PodColumn::Name => a.name.cmp(&b.name),
+            PodColumn::Status => a.status.cmp(&b.status),
+            PodColumn::Node => a.node.cmp(&b.node),
+            PodColumn::Ip => a.ip.cmp(&b.ip),
+        }
+    }
+}
+
+impl FromStr for PodColumn {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PodColumn::ALL
+            .into_iter()
+            .find(|it| it.title().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::UnknownColumn(s.to_owned()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use crate::errors::Error;
 
-    use super::Pod;
+    use super::{count_by_status, Pod, PodColumn, PodHealth, Readiness};
 
     #[test]
     fn parse_pod_info() {
@@ -67,10 +266,13 @@ mod tests {
             "wiremock-docker-64962f5dh4-scq24               1/1     Running   0              18d";
         let expected = Pod {
             name: "wiremock-docker-64962f5dh4-scq24".to_string(),
-            ready: "1/1".to_string(),
+            ready: Readiness { ready: 1, total: 1 },
             status: "Running".to_string(),
-            restarts: "0".to_string(),
-            age: "18d".to_string(),
+            restarts: 0,
+            last_restart: None,
+            age: Some(Duration::from_secs(18 * 24 * 3600)),
+            ip: "".to_string(),
+            node: "".to_string(),
         };
 
         // when
@@ -86,10 +288,13 @@ mod tests {
             "some-7dd394858c-sxctv                       1/1     Running   1 (8d ago)     30d";
         let expected = Pod {
             name: "some-7dd394858c-sxctv".to_string(),
-            ready: "1/1".to_string(),
+            ready: Readiness { ready: 1, total: 1 },
             status: "Running".to_string(),
-            restarts: "1 (8d ago)".to_string(),
-            age: "30d".to_string(),
+            restarts: 1,
+            last_restart: Some(Duration::from_secs(8 * 24 * 3600)),
+            age: Some(Duration::from_secs(30 * 24 * 3600)),
+            ip: "".to_string(),
+            node: "".to_string(),
         };
 
         // when
@@ -99,6 +304,100 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn parse_wide_pod_info() {
+        //given
+        let pod_info = "api-7dd394858c-sxctv   1/1   Running   1 (8d ago)   30d   10.244.0.12   node-1   <none>   <none>";
+
+        // when
+        let actual: Pod = pod_info.parse().unwrap();
+
+        //then
+        assert_eq!(actual.restarts_description(), "1 (8d ago)");
+        assert_eq!(actual.ip, "10.244.0.12");
+        assert_eq!(actual.node, "node-1");
+    }
+
+    #[test]
+    fn keep_pods_with_an_invalid_age() {
+        //given
+        let pod_info = "api-1   1/1   Running   2 (<invalid> ago)   <invalid>";
+
+        // when
+        let actual: Pod = pod_info.parse().unwrap();
+
+        //then
+        assert_eq!(actual.age, None);
+        assert_eq!(actual.age_description(), "<unknown>");
+        assert_eq!(actual.restarts_description(), "2");
+    }
+
+    #[test]
+    fn compare_typed_columns() {
+        //given
+        let young: Pod = "api-1   0/1   Running   10   5m30s".parse().unwrap();
+        let old: Pod = "api-2   1/1   Running   9 (2d ago)   2d3h".parse().unwrap();
+
+        //then
+        assert!(PodColumn::Restarts.compare(&young, &old).is_gt());
+        assert!(PodColumn::Age.compare(&young, &old).is_lt());
+        assert!(!young.ready.is_fully_ready());
+        assert_eq!(PodColumn::Age.value(&young), "5m30s");
+    }
+
+    #[test]
+    fn sort_pods_of_unknown_age_after_the_oldest() {
+        //given
+        let old: Pod = "api-1   1/1   Running   0   120d".parse().unwrap();
+        let unknown: Pod = "api-2   1/1   Running   0   <invalid>".parse().unwrap();
+
+        //then
+        assert!(PodColumn::Age.compare(&unknown, &old).is_gt());
+        assert!(PodColumn::Age.compare(&old, &unknown).is_lt());
+        assert!(PodColumn::Age.compare(&unknown, &unknown).is_eq());
+    }
+
+    #[test]
+    fn classify_statuses() {
+        assert_eq!(PodHealth::of_status("Running"), PodHealth::Healthy);
+        assert_eq!(
+            PodHealth::of_status("ContainerCreating"),
+            PodHealth::Pending
+        );
+        assert_eq!(PodHealth::of_status("Init:0/2"), PodHealth::Pending);
+        assert_eq!(
+            PodHealth::of_status("Init:CrashLoopBackOff"),
+            PodHealth::Failing
+        );
+        assert_eq!(PodHealth::of_status("OOMKilled"), PodHealth::Failing);
+        assert_eq!(PodHealth::of_status("Terminating"), PodHealth::Terminating);
+    }
+
+    #[test]
+    fn count_failing_statuses_first() {
+        //given
+        let pods: Vec<Pod> = [
+            "api-1   1/1   Running            0   5m",
+            "api-2   0/1   CrashLoopBackOff   7   5m",
+            "api-3   1/1   Running            0   5m",
+            "job-1   0/1   Completed          0   1h",
+        ]
+        .iter()
+        .map(|it| it.parse().unwrap())
+        .collect();
+
+        // when
+        let counts = count_by_status(&pods);
+
+        //then
+        assert_eq!(
+            counts,
+            vec![("CrashLoopBackOff", 1), ("Completed", 1), ("Running", 2)]
+        );
+        assert!(pods[1].is_not_ready());
+        assert!(!pods[3].is_not_ready());
+    }
+
     #[test]
     fn should_not_parse_headers() {
         //given
@@ -0,0 +1,149 @@
+/// Any kind of resource as printed by `kubectl get`, using the server-side printer columns.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResourceTable {
+    kind: String,
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl ResourceTable {
+    /// Columns are cut at the header positions, as values can contain spaces (ex: `1 (8d ago)`).
+    pub fn parse(kind: &str, output: &str) -> ResourceTable {
+        let mut lines = output.lines().filter(|it| !it.trim().is_empty());
+
+        let header: Vec<char> = lines.next().unwrap_or_default().chars().collect();
+        let starts = column_starts(&header);
+
+        let headers = cut_columns(&header, &starts);
+        let rows = lines
+            .map(|line| cut_columns(&line.chars().collect::<Vec<_>>(), &starts))
+            .collect();
+
+        ResourceTable {
+            kind: kind.to_owned(),
+            headers,
+            rows,
+        }
+    }
+
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    pub fn headers(&self) -> &Vec<String> {
+        &self.headers
+    }
+
+    pub fn rows(&self) -> &Vec<Vec<String>> {
+        &self.rows
+    }
+
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Name of the resource, which kubectl always prints in the first column.
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.rows
+            .get(index)
+            .and_then(|row| row.first())
+            .map(|it| it.as_str())
+    }
+}
+
+/// Header names can contain a single space (ex: `LAST SEEN`), columns are padded with more.
+fn column_starts(header: &[char]) -> Vec<usize> {
+    let mut starts = vec![];
+    let mut spaces = 0;
+
+    for (index, c) in header.iter().enumerate() {
+        if c.is_whitespace() {
+            spaces += 1;
+            continue;
+        }
+        if index == 0 || spaces >= 2 {
+            starts.push(index);
+        }
+        spaces = 0;
+    }
+    starts
+}
+
+fn cut_columns(line: &[char], starts: &[usize]) -> Vec<String> {
+    starts
+        .iter()
+        .enumerate()
+        .map(|(column, start)| {
+            let end = starts.get(column + 1).copied().unwrap_or(line.len());
+            line.get(*start..end.min(line.len()))
+                .map(|it| it.iter().collect::<String>().trim().to_owned())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResourceTable;
+
+    #[test]
+    fn parse_columns_with_spaces() {
+        //given
+        let output = "\
+NAME                    READY   STATUS    RESTARTS      AGE
+api-7dd394858c-sxctv    1/1     Running   1 (8d ago)    30d
+web-64962f5dh4-scq24    0/1     Pending   0             2m
+";
+
+        // when
+        let table = ResourceTable::parse("pods", output);
+
+        //then
+        assert_eq!(
+            table.headers(),
+            &vec!["NAME", "READY", "STATUS", "RESTARTS", "AGE"]
+        );
+        assert_eq!(
+            table.rows()[0],
+            vec![
+                "api-7dd394858c-sxctv",
+                "1/1",
+                "Running",
+                "1 (8d ago)",
+                "30d"
+            ]
+        );
+        assert_eq!(table.name(1), Some("web-64962f5dh4-scq24"));
+    }
+
+    #[test]
+    fn keep_multi_word_headers() {
+        //given
+        let output = "\
+LAST SEEN   TYPE      REASON    OBJECT        MESSAGE
+2m          Warning   BackOff   pod/api-1     Back-off restarting failed container
+";
+
+        // when
+        let table = ResourceTable::parse("events", output);
+
+        //then
+        assert_eq!(
+            table.headers(),
+            &vec!["LAST SEEN", "TYPE", "REASON", "OBJECT", "MESSAGE"]
+        );
+        assert_eq!(table.rows()[0][4], "Back-off restarting failed container");
+    }
+
+    #[test]
+    fn parse_empty_output() {
+        let table = ResourceTable::parse("ingresses", "");
+
+        assert!(table.is_empty());
+        assert!(table.headers().is_empty());
+    }
+}
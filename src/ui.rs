@@ -1,69 +1,304 @@
 use std::{
     error::Error,
+    fs,
     io::{self, Stdout},
-    time::Duration,
+    panic,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
 };
 
 use crate::{
-    app::App,
-    input::{self, event_loop::EventLoop, InputAction},
+    app::{App, JobKind, JobRequest, KubectlCommand},
+    config::{Config, PODS_TABLE_WIDTHS},
+    editor,
+    input::{
+        self,
+        command::{self, Command},
+        event_loop::{Event, EventLoop},
+        keymap::Keymap,
+        InputAction,
+    },
+    manifest::Manifest,
+    node::NodeOperation,
+    pod::PodColumn,
 };
 use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
+    event::{DisableMouseCapture, EnableMouseCapture, KeyCode, KeyEvent},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use signal_hook::{consts::SIGTSTP, low_level};
 use tui::{
     backend::{Backend, CrosstermBackend},
     layout::{Constraint, Direction, Layout, Rect},
-    widgets::ListState,
-    Terminal,
+    widgets::{Clear, ListState, TableState},
+    Frame, Terminal,
 };
 
-use self::logs::LoggerWidget;
+use self::{
+    help::{Help, HelpResult},
+    logs::LoggerWidget,
+    manifest::ManifestWidget,
+    picker::{Picker, PickerKind, PickerResult},
+    popup::{Confirmation, Popup, PopupResult},
+    prompt::{Prompt, PromptKind, PromptResult},
+    theme::Theme,
+    widgets::table_page_size,
+    workspace::Workspace,
+};
+
+pub use self::job::WorkerEvent;
 
+mod deployments;
+mod events;
 mod footer;
 mod header;
+mod help;
+mod job;
 mod logs;
 mod main_body;
+mod manifest;
+mod metrics;
+mod mouse;
+mod nodes;
+mod picker;
+mod popup;
+mod prompt;
+mod resources;
+pub mod theme;
+mod widgets;
+mod workspace;
+
+/// What is shown in the body of the screen.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum View {
+    Pods,
+    Deployments,
+    Resources,
+    Events,
+    Nodes,
+    Manifest,
+}
+
+impl View {
+    pub const ALL: [View; 6] = [
+        View::Pods,
+        View::Deployments,
+        View::Resources,
+        View::Events,
+        View::Nodes,
+        View::Manifest,
+    ];
+
+    /// Views which can be switched between with the number keys.
+    pub const TABS: [View; 5] = [
+        View::Pods,
+        View::Deployments,
+        View::Resources,
+        View::Events,
+        View::Nodes,
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            View::Pods => "Pods",
+            View::Deployments => "Deployments",
+            View::Resources => "Resources",
+            View::Events => "Events",
+            View::Nodes => "Nodes",
+            View::Manifest => "Manifest",
+        }
+    }
+
+    /// Action switching to the view, the manifest is only opened for the selected item.
+    pub fn show_action(&self) -> Option<InputAction> {
+        match self {
+            View::Pods => Some(InputAction::ShowPods),
+            View::Deployments => Some(InputAction::ShowDeployments),
+            View::Resources => Some(InputAction::ShowResources),
+            View::Events => Some(InputAction::ShowEvents),
+            View::Nodes => Some(InputAction::ShowNodes),
+            View::Manifest => None,
+        }
+    }
+
+    pub fn tab_index(&self) -> usize {
+        View::TABS.iter().position(|it| it == self).unwrap_or(0)
+    }
+}
+
+/// How much the pods table grows or shrinks at once, in percent of the body.
+const PODS_LIST_STEP: u16 = 5;
+
+/// How long a failure stays in the status bar.
+const STATUS_DURATION: Duration = Duration::from_secs(10);
+
+/// What the app does on a signal it catches.
+#[derive(Debug, PartialEq)]
+enum SignalAction {
+    Suspend,
+    Quit,
+}
+
+impl SignalAction {
+    fn of(signal: i32) -> SignalAction {
+        match signal {
+            SIGTSTP => SignalAction::Suspend,
+            _ => SignalAction::Quit,
+        }
+    }
+}
+
+/// Panes of the pods view, in the order Tab cycles through them.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Pane {
+    PodList,
+    Details,
+    Logs,
+}
+
+impl Pane {
+    const ALL: [Pane; 3] = [Pane::PodList, Pane::Details, Pane::Logs];
+
+    fn next(&self) -> Pane {
+        let index = Pane::ALL.iter().position(|it| it == self).unwrap_or(0);
+        Pane::ALL[(index + 1) % Pane::ALL.len()]
+    }
+
+    fn previous(&self) -> Pane {
+        let index = Pane::ALL.iter().position(|it| it == self).unwrap_or(0);
+        Pane::ALL[(index + Pane::ALL.len() - 1) % Pane::ALL.len()]
+    }
+}
 
 pub struct UI<'a> {
-    pod_list_state: ListState,
-    event_loop: EventLoop,
+    pod_table_state: TableState,
+    pod_columns: Vec<PodColumn>,
+    /// Pane of the pods view taking the arrow and page keys.
+    focus: Pane,
+    /// First pod shown in the table, which is what a click on its first row selects.
+    pods_offset: usize,
+    last_click: Option<(Instant, usize)>,
+    /// Percent of the body taken by the pods table, its width or height on narrow screens.
+    pods_list_size: u16,
+    logs_maximised: bool,
+    config: Config,
+    theme: Theme,
+    keymap: Keymap,
+    event_loop: EventLoop<WorkerEvent>,
     app: &'a mut App,
     selected_pod_index: usize,
+    deployment_list_state: ListState,
+    selected_deployment_index: usize,
+    resource_list_state: ListState,
+    selected_resource_index: usize,
+    events_table_state: TableState,
+    selected_event_index: usize,
+    events_refreshed_at: Instant,
+    metrics_refreshed_at: Option<Instant>,
+    node_list_state: ListState,
+    selected_node_index: usize,
     logger_widget: LoggerWidget,
+    manifest_widget: ManifestWidget,
+    view: View,
+    previous_view: View,
+    prompt: Option<Prompt>,
+    /// Pod filter when its prompt was opened.
+    filter_before_prompt: Option<String>,
+    popup: Option<Popup>,
+    picker: Option<Picker>,
+    help: Option<Help>,
+    /// Workspaces in the background, the active one is in place of its fields above.
+    workspaces: Vec<Workspace>,
+    /// Position of the active workspace in the tab bar.
+    active_workspace: usize,
+    command_history: Vec<String>,
+    /// Last failure of a background refresh, with when it happened.
+    status: Option<(String, Instant)>,
+    spinner_frame: usize,
 }
 
 impl<'a> UI<'a> {
-    pub fn new(app: &mut App) -> UI {
-        let pod_list_state = ListState::default();
-        let selected_pod_index = 0;
+    pub fn new(app: &mut App, config: Config, keymap: Keymap, theme: Theme) -> UI<'_> {
+        let event_loop = EventLoop::start(config.tick_rate());
+        UI::with_event_loop(app, config, keymap, theme, event_loop)
+    }
 
-        let tick_rate = Duration::from_millis(200);
-        let event_loop = EventLoop::start(tick_rate);
+    fn with_event_loop(
+        app: &mut App,
+        config: Config,
+        keymap: Keymap,
+        theme: Theme,
+        event_loop: EventLoop<WorkerEvent>,
+    ) -> UI<'_> {
+        let pod_table_state = TableState::default();
+        let selected_pod_index = 0;
 
-        let logger_widget = LoggerWidget::new(None);
+        let logger_widget = LoggerWidget::new(None, config.log_lines);
+        let manifest_widget = ManifestWidget::new();
 
         UI {
-            pod_list_state,
+            pod_table_state,
+            pod_columns: config.columns.clone(),
+            focus: Pane::PodList,
+            pods_offset: 0,
+            last_click: None,
+            pods_list_size: config.pods_table_width,
+            logs_maximised: false,
+            config,
+            theme,
+            keymap,
             event_loop,
             app,
             selected_pod_index,
+            deployment_list_state: ListState::default(),
+            selected_deployment_index: 0,
+            resource_list_state: ListState::default(),
+            selected_resource_index: 0,
+            events_table_state: TableState::default(),
+            selected_event_index: 0,
+            events_refreshed_at: Instant::now(),
+            metrics_refreshed_at: None,
+            node_list_state: ListState::default(),
+            selected_node_index: 0,
             logger_widget,
+            manifest_widget,
+            view: View::Pods,
+            previous_view: View::Pods,
+            prompt: None,
+            filter_before_prompt: None,
+            popup: None,
+            picker: None,
+            help: None,
+            workspaces: vec![],
+            active_workspace: 0,
+            command_history: vec![],
+            status: None,
+            spinner_frame: 0,
         }
     }
 
-    pub fn start(self: &mut Self) -> Result<(), Box<dyn Error>> {
+    pub fn start(&mut self) -> Result<(), Box<dyn Error>> {
+        install_panic_hook();
         let mut terminal = prepare_terminal()?;
+        let result = self.run(&mut terminal);
+        self.event_loop.shutdown();
+        restore_terminal(terminal)?;
 
-        while *self.app.running() {
-            let context = self.draw_screen(&mut terminal)?;
-            self.handle_input(context)?;
-        }
+        result
+    }
 
-        restore_terminal(terminal)?;
+    fn run(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<(), Box<dyn Error>> {
+        self.start_job(JobRequest::Pods);
 
+        while *self.app.running() {
+            let context = self.draw_screen(terminal)?;
+            self.handle_input(context, terminal)?;
+        }
         Ok(())
     }
 
@@ -74,47 +309,296 @@ impl<'a> UI<'a> {
     where
         B: Backend,
     {
-        let mut logs_chunk_height = 0;
+        let mut context = UiContext::default();
 
         terminal.draw(|rect| {
-            let pods = self
-                .app
-                .pods()
-                .expect("pods are loaded before the app right now");
-            let namespace = self.app.namespace();
-
             let size = rect.size();
             let (header_chunk, body_chunk, footer_chunk) = split_screen_vertically(size);
 
             // Draw header and footer
-            rect.render_widget(header::render_info(namespace), header_chunk);
-            let (about_chunk, keys_chunk) = split_body_horizontally(footer_chunk);
-            rect.render_widget(footer::render_about(), about_chunk);
-            rect.render_widget(footer::render_keys(), keys_chunk);
-
-            // Prepare main body
-            let (left_body_chunk, right_body_chunk) = split_body_horizontally(body_chunk);
-            let (details_chunk, logs_chunk) = split_pod_details_vertically(right_body_chunk);
-
-            let pods_list = main_body::render_pods_list(pods);
-            let selected_pod = self.app.get_pod(self.selected_pod_index);
-            let pod_details = main_body::render_pod_details(selected_pod.clone());
-            self.pod_list_state.select(Some(self.selected_pod_index));
-
-            let pod_logs = self
-                .logger_widget
-                .render_pod_logs(self.app.pod_logs(), &logs_chunk.width);
-
-            // Draw main body
-            rect.render_stateful_widget(pods_list, left_body_chunk, &mut self.pod_list_state);
-            rect.render_widget(pod_details, details_chunk);
-            rect.render_widget(pod_logs, logs_chunk);
+            let (info_chunk, views_chunk) = split_header_horizontally(header_chunk);
+            rect.render_widget(
+                header::render_info(
+                    &self.workspace_titles(),
+                    self.active_workspace,
+                    self.app,
+                    &self.theme,
+                ),
+                info_chunk,
+            );
+            rect.render_widget(
+                header::render_views(&self.tab_view(), &self.keymap, &self.theme),
+                views_chunk,
+            );
+            let (about_chunk, keys_chunk) =
+                split_horizontally(footer_chunk, self.config.list_width);
+            match self
+                .status
+                .as_ref()
+                .filter(|(_, at)| at.elapsed() < STATUS_DURATION)
+            {
+                Some((message, _)) => {
+                    rect.render_widget(footer::render_status(message, &self.theme), about_chunk)
+                }
+                None => rect.render_widget(footer::render_about(&self.theme), about_chunk),
+            }
+            match &self.prompt {
+                Some(prompt) => rect.render_widget(prompt.render(&self.theme), keys_chunk),
+                None => rect.render_widget(
+                    footer::render_keys(&self.view, &self.keymap, &self.theme),
+                    keys_chunk,
+                ),
+            }
+            self.draw_spinner(rect, keys_chunk, JobKind::Namespaces);
+            self.draw_spinner(rect, keys_chunk, JobKind::Contexts);
+
+            match self.view {
+                View::Pods => self.draw_pods(rect, body_chunk, &mut context),
+                View::Deployments => self.draw_deployments(rect, body_chunk),
+                View::Resources => self.draw_resources(rect, body_chunk),
+                View::Events => self.draw_events(rect, body_chunk),
+                View::Nodes => self.draw_nodes(rect, body_chunk),
+                View::Manifest => self.draw_manifest(rect, body_chunk, &mut context),
+            }
+
+            if let Some(picker) = self.picker.as_mut() {
+                let picker_chunk = centered_chunk(50, 70, body_chunk);
+                let (picker_list, picker_state) = picker.render(&self.theme);
+                rect.render_widget(Clear, picker_chunk);
+                rect.render_stateful_widget(picker_list, picker_chunk, picker_state);
+            }
+
+            if let Some(help) = &self.help {
+                let help_chunk = centered_chunk(70, 80, body_chunk);
+                rect.render_widget(Clear, help_chunk);
+                rect.render_widget(help.render(&self.theme), help_chunk);
+            }
 
-            self.reset_logger_widget_if_required();
-            logs_chunk_height = logs_chunk.height;
+            if let Some(popup) = &self.popup {
+                let popup_chunk = centered_chunk(80, 70, body_chunk);
+                rect.render_widget(Clear, popup_chunk);
+                rect.render_widget(popup.render(&self.theme), popup_chunk);
+            }
         })?;
 
-        Ok(UiContext { logs_chunk_height })
+        Ok(context)
+    }
+
+    fn draw_pods<B>(&mut self, rect: &mut Frame<B>, body_chunk: Rect, context: &mut UiContext)
+    where
+        B: Backend,
+    {
+        let pods = self.app.pods();
+
+        // Prepare main body
+        let (left_body_chunk, details_chunk, logs_chunk) = split_pods_view(
+            body_chunk,
+            self.logs_maximised,
+            self.config.narrow_width,
+            self.pods_list_size,
+        );
+        context.pods_chunk = left_body_chunk;
+        context.details_chunk = details_chunk;
+        context.logs_chunk = logs_chunk;
+        let (details_chunk, cpu_chunk, memory_chunk) =
+            split_pod_metrics_horizontally(details_chunk);
+
+        let (pods_table, pods_widths) = main_body::render_pods_table(
+            pods,
+            self.app.get_all_pods_number(),
+            &self.pod_columns,
+            self.app.pod_sort(),
+            self.focus == Pane::PodList,
+            &self.theme,
+        );
+        let pods_table = pods_table.widths(&pods_widths);
+        let selected_pod = self.app.get_pod(self.selected_pod_index);
+        let selected_pod_name = selected_pod.map(|it| it.name.clone()).unwrap_or_default();
+        let pod_metrics = self.app.pod_metrics();
+        let pod_details = main_body::render_pod_details(
+            selected_pod.cloned(),
+            pod_metrics.get(&selected_pod_name),
+            self.focus == Pane::Details,
+            &self.theme,
+        );
+        let cpu_history = pod_metrics.cpu_history(&selected_pod_name);
+        let memory_history = pod_metrics.memory_history(&selected_pod_name);
+        let cpu_sparkline =
+            metrics::render_cpu_history(last_samples(&cpu_history, cpu_chunk), &self.theme);
+        let memory_sparkline = metrics::render_memory_history(
+            last_samples(&memory_history, memory_chunk),
+            &self.theme,
+        );
+        self.pod_table_state.select(Some(self.selected_pod_index));
+
+        let fetch_logs_hint = key_hint(
+            &self.keymap,
+            &View::Pods,
+            &[(InputAction::FetchLogs, "to load pod logs")],
+        );
+        let pod_logs = self.logger_widget.render_pod_logs(
+            self.app.pod_logs(),
+            &fetch_logs_hint,
+            &logs_chunk.width,
+            self.focus == Pane::Logs,
+            &self.theme,
+        );
+
+        // Draw main body
+        if !self.logs_maximised {
+            rect.render_stateful_widget(pods_table, left_body_chunk, &mut self.pod_table_state);
+            rect.render_widget(pod_details, details_chunk);
+            rect.render_widget(cpu_sparkline, cpu_chunk);
+            rect.render_widget(memory_sparkline, memory_chunk);
+        }
+        rect.render_widget(pod_logs, logs_chunk);
+        self.draw_spinner(rect, left_body_chunk, JobKind::Pods);
+        // Pods are deleted from the list, which has no command output
+        self.draw_spinner(rect, left_body_chunk, JobKind::Command);
+        self.draw_spinner(rect, context.details_chunk, JobKind::Metrics);
+        self.draw_spinner(rect, logs_chunk, JobKind::Logs);
+
+        self.follow_pods_scroll(left_body_chunk.height);
+        self.reset_logger_widget_if_required();
+    }
+
+    fn draw_deployments<B>(&mut self, rect: &mut Frame<B>, body_chunk: Rect)
+    where
+        B: Backend,
+    {
+        let (left_body_chunk, right_body_chunk) =
+            split_horizontally(body_chunk, self.config.list_width);
+        let (details_chunk, output_chunk) = split_pod_details_vertically(right_body_chunk);
+
+        let deployments_list =
+            deployments::render_deployments_list(self.app.deployments(), &self.theme);
+        let selected = self.app.get_deployment(self.selected_deployment_index);
+        let deployment_details = deployments::render_deployment_details(selected, &self.theme);
+        let hint = key_hint(
+            &self.keymap,
+            &View::Deployments,
+            &[
+                (InputAction::RolloutStatus, "for the rollout status"),
+                (InputAction::RolloutHistory, "for the rollout history"),
+            ],
+        );
+        let command_output =
+            main_body::render_command_output(self.app.command_output(), &hint, &self.theme);
+        self.deployment_list_state
+            .select(Some(self.selected_deployment_index));
+
+        rect.render_stateful_widget(
+            deployments_list,
+            left_body_chunk,
+            &mut self.deployment_list_state,
+        );
+        rect.render_widget(deployment_details, details_chunk);
+        rect.render_widget(command_output, output_chunk);
+        self.draw_spinner(rect, left_body_chunk, JobKind::Deployments);
+        self.draw_spinner(rect, output_chunk, JobKind::Command);
+    }
+
+    fn draw_resources<B>(&mut self, rect: &mut Frame<B>, body_chunk: Rect)
+    where
+        B: Backend,
+    {
+        let (left_body_chunk, right_body_chunk) =
+            split_horizontally(body_chunk, self.config.list_width);
+        let (details_chunk, output_chunk) = split_pod_details_vertically(right_body_chunk);
+
+        let resources_list = resources::render_resources_list(self.app.resources(), &self.theme);
+        let (resource_details, widths) = resources::render_resource_details(
+            self.app.resources(),
+            self.selected_resource_index,
+            &self.theme,
+        );
+        let hint = key_hint(
+            &self.keymap,
+            &View::Resources,
+            &[(InputAction::DescribeResource, "to describe the resource")],
+        );
+        let command_output =
+            main_body::render_command_output(self.app.command_output(), &hint, &self.theme);
+        self.resource_list_state
+            .select(Some(self.selected_resource_index));
+
+        rect.render_stateful_widget(
+            resources_list,
+            left_body_chunk,
+            &mut self.resource_list_state,
+        );
+        rect.render_widget(resource_details.widths(&widths), details_chunk);
+        rect.render_widget(command_output, output_chunk);
+        self.draw_spinner(rect, left_body_chunk, JobKind::Resources);
+        self.draw_spinner(rect, left_body_chunk, JobKind::ResourceKinds);
+        self.draw_spinner(rect, output_chunk, JobKind::Command);
+    }
+
+    fn draw_events<B>(&mut self, rect: &mut Frame<B>, body_chunk: Rect)
+    where
+        B: Backend,
+    {
+        let events =
+            events::render_events(self.app.events(), self.app.events_pod_name(), &self.theme);
+        self.events_table_state
+            .select(Some(self.selected_event_index));
+
+        rect.render_stateful_widget(events, body_chunk, &mut self.events_table_state);
+        self.draw_spinner(rect, body_chunk, JobKind::Events);
+    }
+
+    fn draw_nodes<B>(&mut self, rect: &mut Frame<B>, body_chunk: Rect)
+    where
+        B: Backend,
+    {
+        let (left_body_chunk, right_body_chunk) =
+            split_horizontally(body_chunk, self.config.list_width);
+        let (details_chunk, output_chunk) = split_pod_details_vertically(right_body_chunk);
+
+        let nodes_list = nodes::render_nodes_list(self.app.nodes(), &self.theme);
+        let selected = self.app.get_node(self.selected_node_index);
+        let node_details = nodes::render_node_details(selected, &self.theme);
+        let hint = key_hint(
+            &self.keymap,
+            &View::Nodes,
+            &[
+                (InputAction::CordonNode, "to cordon"),
+                (InputAction::UncordonNode, "to uncordon"),
+                (InputAction::DrainNode, "to drain the node"),
+            ],
+        );
+        let command_output =
+            main_body::render_command_output(self.app.command_output(), &hint, &self.theme);
+        self.node_list_state.select(Some(self.selected_node_index));
+
+        rect.render_stateful_widget(nodes_list, left_body_chunk, &mut self.node_list_state);
+        rect.render_widget(node_details, details_chunk);
+        rect.render_widget(command_output, output_chunk);
+        self.draw_spinner(rect, left_body_chunk, JobKind::Nodes);
+        self.draw_spinner(rect, output_chunk, JobKind::Command);
+    }
+
+    /// View highlighted in the header, the one below the manifest when it is open.
+    fn tab_view(&self) -> View {
+        match self.view {
+            View::Manifest => self.previous_view,
+            view => view,
+        }
+    }
+
+    fn draw_manifest<B>(&mut self, rect: &mut Frame<B>, body_chunk: Rect, context: &mut UiContext)
+    where
+        B: Backend,
+    {
+        if let Some(manifest) = self.app.manifest() {
+            let manifest_view =
+                self.manifest_widget
+                    .render_manifest(manifest, body_chunk.height, &self.theme);
+            rect.render_widget(manifest_view, body_chunk);
+        }
+        self.draw_spinner(rect, body_chunk, JobKind::Manifest);
+        self.draw_spinner(rect, body_chunk, JobKind::Changes);
+        context.manifest_chunk_height = body_chunk.height;
     }
 
     fn reset_logger_widget_if_required(&mut self) {
@@ -122,37 +606,852 @@ impl<'a> UI<'a> {
             .logger_widget
             .should_update_widget(self.app.get_logged_pod_name())
         {
-            self.logger_widget = LoggerWidget::new(self.app.get_logged_pod_name());
+            self.logger_widget =
+                LoggerWidget::new(self.app.get_logged_pod_name(), self.config.log_lines);
         };
     }
 
-    fn handle_input(&mut self, context: UiContext) -> Result<(), Box<dyn Error>> {
-        if let Some(action) = input::map_input(self.event_loop.next()?) {
-            match action {
-                InputAction::NextPod => self.select_next_pod(),
-                InputAction::PreviousPod => self.select_previous_pod(),
-                InputAction::LogsUp => self.logger_widget.page_up(context.logs_chunk_height),
-                InputAction::LogsDown => self.logger_widget.page_down(context.logs_chunk_height),
-                InputAction::LogsEnd => self.logger_widget.end(),
-                _ => {
-                    let context = InputContext {
-                        selected_pod_index: self.selected_pod_index,
-                    };
-                    self.app.take_action(action, context)
+    fn handle_input(
+        &mut self,
+        context: UiContext,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<(), Box<dyn Error>> {
+        let event = self.event_loop.next()?;
+
+        if let Event::Signal(signal) = event {
+            return self.handle_signal(signal, terminal);
+        }
+
+        if let Event::Worker(WorkerEvent::JobDone(result)) = event {
+            self.finish_job(result);
+            return Ok(());
+        }
+
+        if let Event::Tick = event {
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        }
+
+        if self.popup.is_some() {
+            if let Event::Input(key) = event {
+                self.handle_popup_input(key);
+            }
+            return Ok(());
+        }
+
+        if self.prompt.is_some() {
+            if let Event::Input(key) = event {
+                if let Some(action) = self.handle_prompt_input(key) {
+                    self.handle_action(action, &context, terminal)?;
                 }
             }
+            return Ok(());
+        }
+
+        if self.picker.is_some() {
+            if let Event::Input(key) = event {
+                self.handle_picker_input(key);
+            }
+            return Ok(());
+        }
+
+        if let Some(help) = self.help.as_mut() {
+            if let Event::Input(key) = event {
+                if let HelpResult::Closed = help.handle_key(key) {
+                    self.help = None;
+                }
+            }
+            return Ok(());
+        }
+
+        if let Event::Tick = event {
+            self.refresh_events_if_due();
+            self.refresh_metrics_if_due();
+        }
+
+        if let Event::Mouse(mouse) = event {
+            self.handle_mouse(mouse, &context);
+            return Ok(());
+        }
+
+        if let Some(action) = input::map_input(event, &self.view, &self.keymap) {
+            self.handle_action(action, &context, terminal)?;
+        }
+
+        Ok(())
+    }
+
+    fn handle_action(
+        &mut self,
+        action: InputAction,
+        context: &UiContext,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<(), Box<dyn Error>> {
+        match action {
+            InputAction::ShowHelp => self.help = Some(Help::new(self.view, &self.keymap)),
+            InputAction::Suspend => self.suspend(terminal)?,
+            InputAction::OpenCommandPrompt => {
+                self.prompt = Some(Prompt::with_history(
+                    PromptKind::Command,
+                    &self.command_history,
+                ))
+            }
+            InputAction::NewWorkspace => self.prompt = Some(Prompt::new(PromptKind::NewWorkspace)),
+            InputAction::CloseWorkspace => self.close_workspace(),
+            InputAction::NextWorkspace => {
+                self.switch_workspace((self.active_workspace + 1) % (self.workspaces.len() + 1))
+            }
+            InputAction::PreviousWorkspace => {
+                let count = self.workspaces.len() + 1;
+                self.switch_workspace((self.active_workspace + count - 1) % count)
+            }
+            InputAction::FirstItem => self.select_first_item(),
+            InputAction::LastItem => self.select_last_item(),
+            InputAction::NextPane if !self.logs_maximised => self.focus = self.focus.next(),
+            InputAction::PreviousPane if !self.logs_maximised => self.focus = self.focus.previous(),
+            InputAction::GrowPodList => {
+                self.pods_list_size = self
+                    .pods_list_size
+                    .saturating_add(PODS_LIST_STEP)
+                    .min(*PODS_TABLE_WIDTHS.end())
+            }
+            InputAction::ShrinkPodList => {
+                self.pods_list_size = self
+                    .pods_list_size
+                    .saturating_sub(PODS_LIST_STEP)
+                    .max(*PODS_TABLE_WIDTHS.start())
+            }
+            InputAction::ToggleMaximisedLogs => {
+                self.logs_maximised = !self.logs_maximised;
+                self.focus = Pane::Logs;
+            }
+            InputAction::Up | InputAction::Down | InputAction::PageUp | InputAction::PageDown => {
+                self.move_in_focused_pane(action, context)
+            }
+            InputAction::LogsEnd => self.logger_widget.end(),
+            InputAction::SearchManifest => {
+                self.prompt = Some(Prompt::new(PromptKind::ManifestSearch))
+            }
+            InputAction::ShowManifest => {
+                if self.pass_to_app(action) {
+                    self.manifest_widget = ManifestWidget::new();
+                    self.previous_view = self.view;
+                    self.view = View::Manifest;
+                }
+            }
+            InputAction::CloseManifest => {
+                self.pass_to_app(action);
+                self.view = self.previous_view;
+            }
+            InputAction::ShowPods => self.view = View::Pods,
+            InputAction::ShowDeployments => {
+                self.app.clear_command_output();
+                self.start_job(JobRequest::Deployments);
+                self.view = View::Deployments;
+            }
+            InputAction::ShowResources => {
+                self.app.clear_command_output();
+                self.view = View::Resources;
+                match self.app.resources().map(|it| it.kind().to_owned()) {
+                    Some(kind) => self.start_job(JobRequest::Resources(kind)),
+                    None => self.open_resource_kind_picker(),
+                }
+            }
+            InputAction::ShowEvents => {
+                self.show_events(self.app.events_pod_name().map(|it| it.to_owned()))
+            }
+            InputAction::FilterPods => self.open_pod_filter(),
+            InputAction::SortPods => {
+                let (column, _) = self.app.pod_sort();
+                let next = self
+                    .pod_columns
+                    .iter()
+                    .position(|it| *it == column)
+                    .map(|index| (index + 1) % self.pod_columns.len())
+                    .unwrap_or(0);
+                if let Some(next) = self.pod_columns.get(next) {
+                    self.app.sort_pods(*next, false);
+                }
+            }
+            InputAction::ReverseSortPods => {
+                let (column, descending) = self.app.pod_sort();
+                self.app.sort_pods(column, !descending);
+            }
+            InputAction::PickPodColumns => self.open_pod_columns_picker(),
+            InputAction::SetLabelSelector => {
+                let selector = self.app.pod_selector().unwrap_or_default();
+                self.prompt = Some(Prompt::with_input(PromptKind::LabelSelector, selector))
+            }
+            InputAction::SetFieldSelector => {
+                let selector = self.app.pod_field_selector().unwrap_or_default();
+                self.prompt = Some(Prompt::with_input(PromptKind::FieldSelector, selector))
+            }
+            InputAction::ShowPodEvents => {
+                let pod_name = self
+                    .app
+                    .get_pod_name(self.selected_pod_index)
+                    .map(|it| it.to_owned());
+                self.show_events(pod_name);
+            }
+            InputAction::ToggleEventsFilter => {
+                let pod_name = match self.app.events_pod_name() {
+                    Some(_) => None,
+                    None => self
+                        .app
+                        .get_pod_name(self.selected_pod_index)
+                        .map(|it| it.to_owned()),
+                };
+                self.show_events(pod_name);
+            }
+            InputAction::NextEvent => {
+                if self.selected_event_index + 1 < self.app.get_events_number() {
+                    self.selected_event_index += 1;
+                }
+            }
+            InputAction::PreviousEvent => {
+                self.selected_event_index = self.selected_event_index.saturating_sub(1)
+            }
+            InputAction::PickResourceKind => self.open_resource_kind_picker(),
+            InputAction::NextResource => self.select_next_resource(),
+            InputAction::PreviousResource => self.select_previous_resource(),
+            InputAction::NextDeployment => self.select_next_deployment(),
+            InputAction::PreviousDeployment => self.select_previous_deployment(),
+            InputAction::ShowNodes => {
+                self.app.clear_command_output();
+                self.start_job(JobRequest::Nodes);
+                self.view = View::Nodes;
+            }
+            InputAction::NextNode => self.select_next_node(),
+            InputAction::PreviousNode => self.select_previous_node(),
+            InputAction::CordonNode => self.confirm_node_operation(NodeOperation::Cordon),
+            InputAction::UncordonNode => self.confirm_node_operation(NodeOperation::Uncordon),
+            InputAction::DrainNode => self.confirm_node_operation(NodeOperation::Drain),
+            InputAction::ShowDeploymentPods
+            | InputAction::ShowNodePods
+            | InputAction::ClearPodSelector => {
+                self.app.set_pod_filter(None);
+                self.pass_to_app(action);
+                self.selected_pod_index = 0;
+                self.view = View::Pods;
+            }
+            InputAction::ScaleDeployment => {
+                self.prompt = Some(Prompt::new(PromptKind::ScaleDeployment))
+            }
+            InputAction::UndoRollout => self.prompt = Some(Prompt::new(PromptKind::UndoRollout)),
+            InputAction::RestartDeployment => {
+                if let Some(name) = self.selected_deployment_name() {
+                    self.popup = Some(Popup::confirm(
+                        "Restart deployment?",
+                        &format!("All pods of '{name}' will be restarted."),
+                        Confirmation::RestartDeployment(name),
+                    ));
+                }
+            }
+            InputAction::RolloutStatus => {
+                self.rollout_selected_deployment("status", vec!["--watch=false".to_owned()])
+            }
+            InputAction::RolloutHistory => self.rollout_selected_deployment("history", vec![]),
+            InputAction::EditManifest => self.edit_manifest(terminal)?,
+            _ => {
+                if let Some(manifest) = self.app.manifest() {
+                    handle_manifest_action(&mut self.manifest_widget, manifest, &action, context);
+                }
+                self.pass_to_app(action);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Commands standing for an action are returned, to be handled the same way as its keys.
+    fn handle_prompt_input(&mut self, key: KeyEvent) -> Option<InputAction> {
+        let prompt = self.prompt.as_mut()?;
+
+        let kind = prompt.kind();
+        if kind == PromptKind::Command && key.code == KeyCode::Tab {
+            self.complete_command();
+            return None;
+        }
+
+        match prompt.handle_key(key) {
+            PromptResult::Pending => {
+                // pods are narrowed down while typing
+                if kind == PromptKind::PodFilter {
+                    let filter = prompt.input().to_owned();
+                    self.filter_pods(Some(filter));
+                }
+            }
+            PromptResult::Cancelled => {
+                self.prompt = None;
+                if kind == PromptKind::PodFilter {
+                    let filter = self.filter_before_prompt.take();
+                    self.filter_pods(filter);
+                }
+            }
+            PromptResult::Submitted(value) => {
+                self.prompt = None;
+                self.filter_before_prompt = None;
+                match kind {
+                    PromptKind::ManifestSearch => {
+                        if let Some(manifest) = self.app.manifest() {
+                            self.manifest_widget.search(value, manifest);
+                        }
+                    }
+                    PromptKind::ScaleDeployment => self.scale_selected_deployment(&value),
+                    PromptKind::UndoRollout => {
+                        let args = match value.trim() {
+                            "" => vec![],
+                            revision => vec![format!("--to-revision={revision}")],
+                        };
+                        self.rollout_selected_deployment("undo", args);
+                    }
+                    PromptKind::PodFilter => self.filter_pods(Some(value)),
+                    PromptKind::LabelSelector => {
+                        let field_selector = self.app.pod_field_selector().map(|it| it.to_owned());
+                        self.select_pods(non_empty(value), field_selector);
+                    }
+                    PromptKind::FieldSelector => {
+                        let selector = self.app.pod_selector().map(|it| it.to_owned());
+                        self.select_pods(selector, non_empty(value));
+                    }
+                    PromptKind::NewWorkspace => self.open_workspace(&value),
+                    PromptKind::Command => return self.submit_command(value),
+                }
+            }
+        }
+        None
+    }
+
+    /// Errors are shown in the prompt, which stays open to fix the command.
+    fn submit_command(&mut self, input: String) -> Option<InputAction> {
+        if !input.trim().is_empty() && self.command_history.last() != Some(&input) {
+            self.command_history.push(input.clone());
+        }
+
+        match input.parse().and_then(|command| self.run_command(command)) {
+            Ok(action) => action,
+            Err(err) => {
+                let mut prompt = Prompt::with_history(PromptKind::Command, &self.command_history);
+                prompt.set_input(input);
+                prompt.set_error(err.to_string());
+                self.prompt = Some(prompt);
+                None
+            }
+        }
+    }
+
+    fn run_command(
+        &mut self,
+        command: Command,
+    ) -> Result<Option<InputAction>, crate::errors::Error> {
+        match command {
+            Command::Action(action) if self.keymap.is_available(&self.view, action) => {
+                return Ok(Some(action))
+            }
+            Command::Action(action) => {
+                return Err(crate::errors::Error::InvalidCommand(format!(
+                    "{} isn't available in this view",
+                    action.name()
+                )))
+            }
+            Command::Namespace(namespace) => {
+                let context = self.app.context().map(|it| it.to_owned());
+                self.replace_workspace(namespace, context);
+            }
+            Command::Context(context) => {
+                // The namespace is kept, unless the config has one for the context
+                let namespace = self
+                    .config
+                    .contexts
+                    .get(&context)
+                    .and_then(|it| it.namespace.clone())
+                    .unwrap_or_else(|| self.app.namespace().to_owned());
+                self.replace_workspace(namespace, Some(context));
+            }
+            Command::Logs { pod_name, since } => self.show_logs(pod_name, since.as_deref())?,
+            Command::Delete(name) => self.confirm_delete(name)?,
+        }
+        Ok(None)
+    }
+
+    /// Namespaces and contexts are loaded in the background the first time they are completed,
+    /// the completion happens once they are.
+    fn complete_command(&mut self) {
+        let input = match &self.prompt {
+            Some(prompt) if prompt.kind() == PromptKind::Command => prompt.input().to_owned(),
+            _ => return,
+        };
+        let request = match command::completed_words(&input).as_slice() {
+            ["ns"] if self.app.namespaces().is_empty() => Some(JobRequest::Namespaces),
+            ["ctx"] if self.app.contexts().is_empty() => Some(JobRequest::Contexts),
+            _ => None,
+        };
+        if let Some(request) = request {
+            if !self.app.is_loading(request.kind()) {
+                self.start_job(request);
+            }
+            return;
+        }
+
+        let completed = command::complete(&input, &self.command_candidates(&input));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.set_input(completed);
+        }
+    }
+
+    /// Commands, then the arguments of the typed one.
+    fn command_candidates(&self, input: &str) -> Vec<String> {
+        match command::completed_words(input).as_slice() {
+            [] => Command::names(),
+            ["ns"] => self.app.namespaces().clone(),
+            ["ctx"] => self.app.contexts().clone(),
+            ["logs", ..] => self
+                .app
+                .pods()
+                .iter()
+                .map(|(pod, _)| pod.name.clone())
+                .chain(["--since".to_owned()])
+                .collect(),
+            ["delete"] => self.listed_names(),
+            _ => vec![],
+        }
+    }
+
+    /// Logs of the named pod, or of the selected one, shown in the pods view.
+    fn show_logs(
+        &mut self,
+        pod_name: Option<String>,
+        since: Option<&str>,
+    ) -> Result<(), crate::errors::Error> {
+        if let Some(index) = pod_name.as_deref().and_then(|it| self.app.find_pod(it)) {
+            self.selected_pod_index = index;
+        }
+        let pod_name = pod_name
+            .or_else(|| {
+                self.app
+                    .get_pod_name(self.selected_pod_index)
+                    .map(|it| it.to_owned())
+            })
+            .ok_or_else(|| invalid_command("no pod selected"))?;
+
+        self.start_job(JobRequest::Logs {
+            pod_name,
+            since: since.map(|it| it.to_owned()),
+        });
+        self.view = View::Pods;
+        Ok(())
+    }
+
+    fn confirm_delete(&mut self, name: Option<String>) -> Result<(), crate::errors::Error> {
+        let kind = self
+            .listed_kind()
+            .ok_or_else(|| invalid_command("nothing to delete in this view"))?;
+        let name = name
+            .or_else(|| self.selected_name())
+            .ok_or_else(|| invalid_command("nothing selected"))?;
+
+        self.popup = Some(Popup::confirm(
+            &format!("Delete {kind}?"),
+            &format!("'{name}' will be deleted."),
+            Confirmation::DeleteResource(kind, name),
+        ));
+        Ok(())
+    }
+
+    /// Kind of the items listed in the view, for the commands acting on them.
+    fn listed_kind(&self) -> Option<String> {
+        match self.view {
+            View::Pods => Some("pod".to_owned()),
+            View::Deployments => Some("deployment".to_owned()),
+            View::Resources => self.app.resources().map(|it| it.kind().to_owned()),
+            _ => None,
+        }
+    }
+
+    fn listed_names(&self) -> Vec<String> {
+        match self.view {
+            View::Pods => self
+                .app
+                .pods()
+                .iter()
+                .map(|(pod, _)| pod.name.clone())
+                .collect(),
+            View::Deployments => self
+                .app
+                .deployments()
+                .iter()
+                .map(|it| it.name.clone())
+                .collect(),
+            View::Resources => (0..self.app.get_resources_number())
+                .flat_map(|index| self.app.resources().and_then(|it| it.name(index)))
+                .map(|it| it.to_owned())
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    fn selected_name(&self) -> Option<String> {
+        match self.view {
+            View::Pods => self
+                .app
+                .get_pod_name(self.selected_pod_index)
+                .map(|it| it.to_owned()),
+            View::Deployments => self.selected_deployment_name(),
+            View::Resources => self
+                .app
+                .resources()
+                .and_then(|it| it.name(self.selected_resource_index))
+                .map(|it| it.to_owned()),
+            _ => None,
+        }
+    }
+
+    fn handle_picker_input(&mut self, key: KeyEvent) {
+        let result = match self.picker.as_mut() {
+            Some(picker) => picker.handle_key(key),
+            None => return,
+        };
+
+        match result {
+            PickerResult::Pending => {}
+            PickerResult::Cancelled => self.picker = None,
+            PickerResult::Picked(value) => {
+                let kind = self.picker.take().map(|it| it.kind());
+                match kind {
+                    Some(PickerKind::PodColumn) => {
+                        let column = value.split_whitespace().next().unwrap_or_default();
+                        if let Ok(column) = column.parse() {
+                            self.toggle_pod_column(column);
+                        }
+                    }
+                    Some(PickerKind::ResourceKind) => {
+                        self.app.clear_command_output();
+                        self.selected_resource_index = 0;
+                        self.start_job(JobRequest::Resources(value));
+                    }
+                    None => {}
+                }
+            }
+        }
+    }
+
+    fn select_pods(&mut self, selector: Option<String>, field_selector: Option<String>) {
+        self.start_job(JobRequest::SelectPods {
+            selector,
+            field_selector,
+        });
+        self.selected_pod_index = 0;
+    }
+
+    /// Selection stays on the same pod as long as it passes the filter.
+    /// The filter is edited in place, the one it started from is back when the edit is cancelled.
+    fn open_pod_filter(&mut self) {
+        let filter = self.app.pod_filter().map(|it| it.to_owned());
+        self.prompt = Some(Prompt::with_input(
+            PromptKind::PodFilter,
+            filter.as_deref().unwrap_or_default(),
+        ));
+        self.filter_before_prompt = filter;
+    }
+
+    fn filter_pods(&mut self, filter: Option<String>) {
+        let selected_pod_name = self
+            .app
+            .get_pod_name(self.selected_pod_index)
+            .map(|it| it.to_owned());
+        self.app.set_pod_filter(filter);
+        self.selected_pod_index = selected_pod_name
+            .and_then(|name| self.app.find_pod(&name))
+            .unwrap_or(0);
+    }
+
+    fn show_events(&mut self, pod_name: Option<String>) {
+        self.app.set_events_pod_name(pod_name);
+        self.selected_event_index = 0;
+        self.view = View::Events;
+
+        self.start_job(JobRequest::Events);
+        self.events_refreshed_at = Instant::now();
+    }
+
+    fn open_pod_columns_picker(&mut self) {
+        let options = PodColumn::ALL
+            .iter()
+            .map(|column| match self.pod_columns.contains(column) {
+                true => column.title().to_owned(),
+                false => format!("{} (hidden)", column.title()),
+            })
+            .collect();
+        self.picker = Some(Picker::new(PickerKind::PodColumn, options));
+    }
+
+    /// Columns keep their default order, the name can't be hidden.
+    fn toggle_pod_column(&mut self, column: PodColumn) {
+        if column == PodColumn::Name {
+            return;
+        }
+        match self.pod_columns.contains(&column) {
+            true => self.pod_columns.retain(|it| *it != column),
+            false => {
+                self.pod_columns.push(column);
+                self.pod_columns
+                    .sort_by_key(|it| PodColumn::ALL.iter().position(|all| all == it));
+            }
+        }
+    }
+
+    /// Kinds are discovered in the background the first time, the picker opens once they are.
+    fn open_resource_kind_picker(&mut self) {
+        let kinds = self.app.resource_kinds();
+        if kinds.is_empty() {
+            if !self.app.is_loading(JobKind::ResourceKinds) {
+                self.start_job(JobRequest::ResourceKinds);
+            }
+            return;
+        }
+        self.picker = Some(Picker::new(PickerKind::ResourceKind, kinds.clone()));
+    }
+
+    fn handle_popup_input(&mut self, key: KeyEvent) {
+        let result = match self.popup.as_mut() {
+            Some(popup) => popup.handle_key(key),
+            None => return,
         };
 
+        match result {
+            PopupResult::Pending => {}
+            PopupResult::Closed => self.popup = None,
+            PopupResult::Confirmed(Confirmation::ApplyManifest(path)) => {
+                self.popup = None;
+                self.start_job(JobRequest::ApplyChanges(path));
+            }
+            PopupResult::Rejected(Confirmation::ApplyManifest(path)) => {
+                remove_temp_file(&path);
+                self.popup = None;
+            }
+            PopupResult::Confirmed(Confirmation::RestartDeployment(name)) => {
+                self.popup = None;
+                self.start_job(JobRequest::Command(KubectlCommand::Rollout {
+                    name,
+                    subcommand: "restart".to_owned(),
+                    args: vec![],
+                }));
+            }
+            PopupResult::Confirmed(Confirmation::OperateOnNode(operation, name)) => {
+                self.popup = None;
+                self.start_job(JobRequest::Command(KubectlCommand::OperateOnNode(
+                    operation, name,
+                )));
+            }
+            PopupResult::Confirmed(Confirmation::DeleteResource(kind, name)) => {
+                self.popup = None;
+                self.start_job(JobRequest::Command(KubectlCommand::Delete { kind, name }));
+            }
+            PopupResult::Rejected(
+                Confirmation::RestartDeployment(_)
+                | Confirmation::OperateOnNode(_, _)
+                | Confirmation::DeleteResource(_, _),
+            ) => self.popup = None,
+        }
+    }
+
+    /// SIGTSTP suspends the app like Ctrl-Z, the other signals quit it.
+    fn handle_signal(
+        &mut self,
+        signal: i32,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<(), Box<dyn Error>> {
+        match SignalAction::of(signal) {
+            SignalAction::Suspend => self.suspend(terminal),
+            SignalAction::Quit => {
+                self.pass_to_app(InputAction::Quit);
+                Ok(())
+            }
+        }
+    }
+
+    /// Gives the terminal back to the shell until the app is brought back with `fg`.
+    fn suspend(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<(), Box<dyn Error>> {
+        self.event_loop.pause();
+        suspend_terminal(terminal)?;
+        low_level::emulate_default_handler(SIGTSTP)?;
+        resume_terminal(terminal)?;
+        self.event_loop.resume();
         Ok(())
     }
 
+    /// Hands the terminal over to the editor and asks for a confirmation of the changes.
+    fn edit_manifest(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    ) -> Result<(), Box<dyn Error>> {
+        let manifest = match self.app.manifest() {
+            Some(manifest) => manifest,
+            None => return Ok(()),
+        };
+        let path = match editor::write_temp_file(manifest) {
+            Ok(path) => path,
+            Err(err) => {
+                self.popup = Some(Popup::error("Edit failed", &err.to_string()));
+                return Ok(());
+            }
+        };
+
+        self.event_loop.pause();
+        suspend_terminal(terminal)?;
+        let edited = editor::edit_file(&path);
+        resume_terminal(terminal)?;
+        self.event_loop.resume();
+
+        match edited {
+            Ok(()) => self.start_job(JobRequest::ReviewChanges(path)),
+            Err(err) => self.popup = Some(review_popup(path, Err(err))),
+        }
+        Ok(())
+    }
+
+    fn selected_deployment_name(&self) -> Option<String> {
+        self.app
+            .get_deployment(self.selected_deployment_index)
+            .map(|it| it.name.clone())
+    }
+
+    fn confirm_node_operation(&mut self, operation: NodeOperation) {
+        if let Some(node) = self.app.get_node(self.selected_node_index) {
+            self.popup = Some(Popup::confirm(
+                &format!("{} node {}?", operation.command(), node.name),
+                operation.description(),
+                Confirmation::OperateOnNode(operation, node.name.clone()),
+            ));
+        }
+    }
+
+    fn scale_selected_deployment(&mut self, replicas: &str) {
+        let name = match self.selected_deployment_name() {
+            Some(name) => name,
+            None => return,
+        };
+
+        match replicas.trim().parse::<u32>() {
+            Ok(replicas) => self.start_job(JobRequest::Command(KubectlCommand::Scale {
+                name,
+                replicas,
+            })),
+            Err(_) => {
+                self.popup = Some(Popup::error(
+                    "Scale failed",
+                    &format!("'{replicas}' is not a valid number of replicas."),
+                ))
+            }
+        }
+    }
+
+    fn rollout_selected_deployment(&mut self, subcommand: &str, args: Vec<String>) {
+        if let Some(name) = self.selected_deployment_name() {
+            self.start_job(JobRequest::Command(KubectlCommand::Rollout {
+                name,
+                subcommand: subcommand.to_owned(),
+                args,
+            }));
+        }
+    }
+
+    /// A pending confirmation isn't replaced, the failure goes to the status bar then.
+    fn report_error(&mut self, title: &str, err: &crate::errors::Error) {
+        match &self.popup {
+            Some(popup) if popup.is_confirmation() => self.report_status(title, err),
+            _ => self.popup = Some(Popup::error(title, &err.to_string())),
+        }
+    }
+
+    /// Failures of the background refreshes go to the status bar, so they don't interrupt.
+    fn report_status(&mut self, title: &str, err: &crate::errors::Error) {
+        self.status = Some((format!("{title}: {err}"), Instant::now()));
+    }
+
+    /// Returns whether the action started a job.
+    fn pass_to_app(&mut self, action: InputAction) -> bool {
+        let context = InputContext {
+            view: self.view,
+            selected_pod_index: self.selected_pod_index,
+            selected_deployment_index: self.selected_deployment_index,
+            selected_resource_index: self.selected_resource_index,
+            selected_node_index: self.selected_node_index,
+        };
+        match self.app.take_action(action, context) {
+            Some(request) => {
+                self.start_job(request);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn select_first_item(&mut self) {
+        match self.view {
+            View::Pods => self.selected_pod_index = 0,
+            View::Deployments => self.selected_deployment_index = 0,
+            View::Resources => self.selected_resource_index = 0,
+            View::Events => self.selected_event_index = 0,
+            View::Nodes => self.selected_node_index = 0,
+            View::Manifest => {}
+        }
+    }
+
+    fn select_last_item(&mut self) {
+        match self.view {
+            View::Pods => self.selected_pod_index = self.app.get_pods_number().saturating_sub(1),
+            View::Deployments => {
+                self.selected_deployment_index = self.app.get_deployments_number().saturating_sub(1)
+            }
+            View::Resources => {
+                self.selected_resource_index = self.app.get_resources_number().saturating_sub(1)
+            }
+            View::Events => {
+                self.selected_event_index = self.app.get_events_number().saturating_sub(1)
+            }
+            View::Nodes => self.selected_node_index = self.app.get_nodes_number().saturating_sub(1),
+            View::Manifest => {}
+        }
+    }
+
+    /// Details have nothing to scroll, so they keep browsing the pods they describe.
+    fn move_in_focused_pane(&mut self, action: InputAction, context: &UiContext) {
+        let pods_page = table_page_size(context.pods_chunk.height);
+        match (self.focus, action) {
+            (Pane::Logs, InputAction::Up) => self.logger_widget.line_up(),
+            (Pane::Logs, InputAction::Down) => self.logger_widget.line_down(),
+            (Pane::Logs, InputAction::PageUp) => {
+                self.logger_widget.page_up(context.logs_chunk.height)
+            }
+            (Pane::Logs, InputAction::PageDown) => {
+                self.logger_widget.page_down(context.logs_chunk.height)
+            }
+            (_, InputAction::Up) => self.select_previous_pod(),
+            (_, InputAction::Down) => self.select_next_pod(),
+            (_, InputAction::PageUp) => {
+                self.selected_pod_index = self.selected_pod_index.saturating_sub(pods_page)
+            }
+            (_, InputAction::PageDown) => {
+                self.selected_pod_index = (self.selected_pod_index + pods_page)
+                    .min(self.app.get_pods_number().saturating_sub(1))
+            }
+            _ => {}
+        }
+    }
+
     fn select_next_pod(&mut self) {
         let pods_number = self.app.get_pods_number();
 
+        if pods_number == 0 {
+            return;
+        }
         if self.selected_pod_index >= pods_number - 1 {
             self.selected_pod_index = 0;
         } else {
-            self.selected_pod_index = self.selected_pod_index + 1;
+            self.selected_pod_index += 1;
         }
     }
 
@@ -160,13 +1459,106 @@ impl<'a> UI<'a> {
         let pods_number = self.app.get_pods_number();
 
         if self.selected_pod_index > 0 {
-            self.selected_pod_index = self.selected_pod_index - 1;
+            self.selected_pod_index -= 1;
+        } else {
+            self.selected_pod_index = pods_number.saturating_sub(1);
+        }
+    }
+
+    fn select_next_deployment(&mut self) {
+        let deployments_number = self.app.get_deployments_number();
+
+        if self.selected_deployment_index + 1 >= deployments_number {
+            self.selected_deployment_index = 0;
+        } else {
+            self.selected_deployment_index += 1;
+        }
+    }
+
+    fn select_next_resource(&mut self) {
+        let resources_number = self.app.get_resources_number();
+
+        if self.selected_resource_index + 1 >= resources_number {
+            self.selected_resource_index = 0;
+        } else {
+            self.selected_resource_index += 1;
+        }
+    }
+
+    fn select_previous_resource(&mut self) {
+        let resources_number = self.app.get_resources_number();
+
+        if self.selected_resource_index > 0 {
+            self.selected_resource_index -= 1;
+        } else {
+            self.selected_resource_index = resources_number.saturating_sub(1);
+        }
+    }
+
+    fn select_next_node(&mut self) {
+        let nodes_number = self.app.get_nodes_number();
+
+        if self.selected_node_index + 1 >= nodes_number {
+            self.selected_node_index = 0;
+        } else {
+            self.selected_node_index += 1;
+        }
+    }
+
+    fn select_previous_node(&mut self) {
+        let nodes_number = self.app.get_nodes_number();
+
+        if self.selected_node_index > 0 {
+            self.selected_node_index -= 1;
+        } else {
+            self.selected_node_index = nodes_number.saturating_sub(1);
+        }
+    }
+
+    fn select_previous_deployment(&mut self) {
+        let deployments_number = self.app.get_deployments_number();
+
+        if self.selected_deployment_index > 0 {
+            self.selected_deployment_index -= 1;
         } else {
-            self.selected_pod_index = pods_number - 1;
+            self.selected_deployment_index = deployments_number.saturating_sub(1);
         }
     }
 }
 
+fn handle_manifest_action(
+    widget: &mut ManifestWidget,
+    manifest: &Manifest,
+    action: &InputAction,
+    context: &UiContext,
+) {
+    match action {
+        InputAction::ManifestUp => widget.up(),
+        InputAction::ManifestDown => widget.down(manifest),
+        InputAction::ManifestPageUp => widget.page_up(context.manifest_chunk_height),
+        InputAction::ManifestPageDown => widget.page_down(manifest, context.manifest_chunk_height),
+        InputAction::ToggleFold => widget.toggle_fold(manifest),
+        InputAction::ToggleNoise => widget.toggle_noise(manifest),
+        InputAction::NextMatch => widget.next_match(manifest),
+        InputAction::PreviousMatch => widget.previous_match(manifest),
+        _ => {}
+    }
+}
+
+/// Restores the terminal before a panic of the UI thread is reported, so the message can be
+/// read. The UI keeps running after a panic of another thread, ex: of a job, which fails it.
+fn install_panic_hook() {
+    let hook = panic::take_hook();
+    let ui_thread = thread::current().id();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().id() == ui_thread {
+            let _ = disable_raw_mode();
+            let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
+        }
+        hook(info);
+    }));
+}
+
 fn prepare_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>, Box<dyn Error>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -178,6 +1570,12 @@ fn prepare_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>, Box<dyn Erro
 
 fn restore_terminal(
     mut terminal: Terminal<CrosstermBackend<Stdout>>,
+) -> Result<(), Box<dyn Error>> {
+    suspend_terminal(&mut terminal)
+}
+
+fn suspend_terminal(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
 ) -> Result<(), Box<dyn Error>> {
     disable_raw_mode()?;
     execute!(
@@ -189,13 +1587,71 @@ fn restore_terminal(
     Ok(())
 }
 
+fn resume_terminal(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+) -> Result<(), Box<dyn Error>> {
+    enable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableMouseCapture
+    )?;
+    terminal.clear()?;
+    Ok(())
+}
+
+fn invalid_command(message: &str) -> crate::errors::Error {
+    crate::errors::Error::InvalidCommand(message.to_owned())
+}
+
+fn non_empty(value: String) -> Option<String> {
+    match value.trim() {
+        "" => None,
+        value => Some(value.to_owned()),
+    }
+}
+
+fn remove_temp_file(path: &Path) {
+    // Leftovers in the temp directory are harmless
+    let _ = fs::remove_file(path);
+}
+
+fn kept_changes_message(err: crate::errors::Error, path: &Path) -> String {
+    format!("{err}\n\nYour changes are kept in {}", path.display())
+}
+
+/// Asks for a confirmation of the reviewed changes, the file is removed when there are none.
+fn review_popup(path: PathBuf, diff: Result<Option<String>, crate::errors::Error>) -> Popup {
+    match diff {
+        Ok(None) => {
+            remove_temp_file(&path);
+            Popup::info("Edit", "No changes to apply.")
+        }
+        Ok(Some(diff)) => {
+            Popup::confirm("Apply changes?", &diff, Confirmation::ApplyManifest(path))
+        }
+        Err(err) => Popup::error("Invalid changes", &kept_changes_message(err, &path)),
+    }
+}
+
+/// The file is removed once the changes are applied, and kept to fix them otherwise.
+fn apply_popup(path: &Path, output: Result<String, crate::errors::Error>) -> Popup {
+    match output {
+        Ok(output) => {
+            remove_temp_file(path);
+            Popup::info("Changes applied", &output)
+        }
+        Err(err) => Popup::error("Apply failed", &kept_changes_message(err, path)),
+    }
+}
+
 fn split_screen_vertically(size: Rect) -> (Rect, Rect, Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(0)
         .constraints(
             [
-                Constraint::Length(3),
+                Constraint::Length(4),
                 Constraint::Min(6),
                 Constraint::Length(3),
             ]
@@ -205,14 +1661,91 @@ fn split_screen_vertically(size: Rect) -> (Rect, Rect, Rect) {
     (chunks[0], chunks[1], chunks[2])
 }
 
-fn split_body_horizontally(chunk: Rect) -> (Rect, Rect) {
+fn split_header_horizontally(chunk: Rect) -> (Rect, Rect) {
+    let header_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+        .split(chunk);
+    (header_chunks[0], header_chunks[1])
+}
+
+/// Logs go below the list on narrow screens, and take the whole body when maximised.
+fn split_pods_view(
+    body_chunk: Rect,
+    logs_maximised: bool,
+    narrow_width: u16,
+    list_size: u16,
+) -> (Rect, Rect, Rect) {
+    if logs_maximised {
+        return (Rect::default(), Rect::default(), body_chunk);
+    }
+
+    let (list_chunk, rest_chunk) = match body_chunk.width < narrow_width {
+        true => split_vertically(body_chunk, list_size),
+        false => split_horizontally(body_chunk, list_size),
+    };
+    let (details_chunk, logs_chunk) = split_pod_details_vertically(rest_chunk);
+    (list_chunk, details_chunk, logs_chunk)
+}
+
+/// Splits off the list on the left, taking `left_width` percent of the chunk.
+fn split_horizontally(chunk: Rect, left_width: u16) -> (Rect, Rect) {
+    let left_width = left_width.min(100);
     let pods_chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(20), Constraint::Percentage(80)].as_ref())
+        .constraints(
+            [
+                Constraint::Percentage(left_width),
+                Constraint::Percentage(100 - left_width),
+            ]
+            .as_ref(),
+        )
+        .split(chunk);
+    (pods_chunks[0], pods_chunks[1])
+}
+
+/// Splits off the list on the top, taking `top_height` percent of the chunk.
+fn split_vertically(chunk: Rect, top_height: u16) -> (Rect, Rect) {
+    let top_height = top_height.min(100);
+    let pods_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage(top_height),
+                Constraint::Percentage(100 - top_height),
+            ]
+            .as_ref(),
+        )
         .split(chunk);
     (pods_chunks[0], pods_chunks[1])
 }
 
+fn centered_chunk(percent_x: u16, percent_y: u16, chunk: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_y) / 2),
+                Constraint::Percentage(percent_y),
+                Constraint::Percentage((100 - percent_y) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(chunk);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage((100 - percent_x) / 2),
+                Constraint::Percentage(percent_x),
+                Constraint::Percentage((100 - percent_x) / 2),
+            ]
+            .as_ref(),
+        )
+        .split(vertical[1])[1]
+}
+
 fn split_pod_details_vertically(chunk: Rect) -> (Rect, Rect) {
     let details_chunk = Layout::default()
         .direction(Direction::Vertical)
@@ -221,10 +1754,260 @@ fn split_pod_details_vertically(chunk: Rect) -> (Rect, Rect) {
     (details_chunk[0], details_chunk[1])
 }
 
+/// Details table on the left, CPU and memory history on the right.
+fn split_pod_metrics_horizontally(chunk: Rect) -> (Rect, Rect, Rect) {
+    let metrics_chunk = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(
+            [
+                Constraint::Percentage(70),
+                Constraint::Percentage(15),
+                Constraint::Percentage(15),
+            ]
+            .as_ref(),
+        )
+        .split(chunk);
+    (metrics_chunk[0], metrics_chunk[1], metrics_chunk[2])
+}
+
+/// Sparklines draw from the left, so only the samples fitting inside the borders are kept.
+fn last_samples(history: &[u64], chunk: Rect) -> &[u64] {
+    let width = chunk.width.saturating_sub(2) as usize;
+    &history[history.len().saturating_sub(width)..]
+}
+
 pub struct InputContext {
+    pub view: View,
     pub selected_pod_index: usize,
+    pub selected_deployment_index: usize,
+    pub selected_resource_index: usize,
+    pub selected_node_index: usize,
+}
+
+/// Hint shown in a pane until it has something to show, ex: `Press 'c' to cordon or 'd' to
+/// drain the node.`, with the keys of the keymap or the command of the actions bound to none.
+fn key_hint(keymap: &Keymap, view: &View, actions: &[(InputAction, &str)]) -> String {
+    let mut previous_verb = None;
+    let parts: Vec<_> = actions
+        .iter()
+        .map(|(action, purpose)| {
+            let chords: Vec<_> = keymap
+                .chords(view, *action)
+                .iter()
+                .map(|it| format!("'{it}'"))
+                .collect();
+            let (verb, keys) = match chords.is_empty() {
+                true => ("type", format!("':{}'", action.name())),
+                false => ("press", chords.join(" or ")),
+            };
+            let part = match previous_verb == Some(verb) {
+                true => format!("{keys} {purpose}"),
+                false => format!("{verb} {keys} {purpose}"),
+            };
+            previous_verb = Some(verb);
+            part
+        })
+        .collect();
+
+    let hint = match parts.split_last() {
+        Some((last, [])) => last.clone(),
+        Some((last, rest)) => format!("{} or {last}", rest.join(", ")),
+        None => String::new(),
+    };
+    let mut chars = hint.chars();
+    match chars.next() {
+        Some(first) => format!("{}{}.", first.to_uppercase(), chars.as_str()),
+        None => hint,
+    }
 }
 
+/// Where the panes were drawn, for the keys and clicks handled before the next draw.
+#[derive(Default)]
 struct UiContext {
-    logs_chunk_height: u16,
+    pods_chunk: Rect,
+    details_chunk: Rect,
+    logs_chunk: Rect,
+    manifest_chunk_height: u16,
+}
+
+#[cfg(test)]
+mod tests {
+    use tui::layout::Rect;
+
+    use signal_hook::consts::{SIGINT, SIGTERM, SIGTSTP};
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::{
+        app::{App, JobKind, JobRequest, JobResult},
+        config::Config,
+        input::{
+            event_loop::EventLoop,
+            keymap::{Keymap, KeymapFile},
+            InputAction,
+        },
+    };
+
+    use super::{
+        command::Command,
+        key_hint,
+        popup::{Confirmation, Popup},
+        split_pods_view,
+        theme::Theme,
+        Pane, SignalAction, View, UI,
+    };
+
+    fn ui(app: &mut App) -> UI<'_> {
+        UI::with_event_loop(
+            app,
+            Config::default(),
+            Keymap::defaults(false),
+            Theme::dark(),
+            EventLoop::new(),
+        )
+    }
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn suspend_on_sigtstp_and_quit_on_the_other_signals() {
+        assert_eq!(SignalAction::of(SIGTSTP), SignalAction::Suspend);
+        assert_eq!(SignalAction::of(SIGINT), SignalAction::Quit);
+        assert_eq!(SignalAction::of(SIGTERM), SignalAction::Quit);
+    }
+
+    #[test]
+    fn cycle_through_the_panes() {
+        // given
+        let pane = Pane::PodList;
+
+        // when
+        let forward = [pane.next(), pane.next().next(), pane.next().next().next()];
+        let backward = [pane.previous(), pane.previous().previous()];
+
+        // then
+        assert_eq!(forward, [Pane::Details, Pane::Logs, Pane::PodList]);
+        assert_eq!(backward, [Pane::Logs, Pane::Details]);
+    }
+
+    #[test]
+    fn give_the_whole_body_to_the_maximised_logs() {
+        // given
+        let body = Rect::new(0, 1, 120, 30);
+
+        // when
+        let (list, details, logs) = split_pods_view(body, true, 100, 40);
+
+        // then
+        assert_eq!(list, Rect::default());
+        assert_eq!(details, Rect::default());
+        assert_eq!(logs, body);
+    }
+
+    #[test]
+    fn restore_the_panes_next_to_the_list() {
+        // given
+        let body = Rect::new(0, 1, 120, 30);
+
+        // when
+        let (list, details, logs) = split_pods_view(body, false, 100, 40);
+        let (narrow_list, _, narrow_logs) =
+            split_pods_view(Rect::new(0, 1, 80, 30), false, 100, 40);
+
+        // then
+        assert_eq!(list, Rect::new(0, 1, 48, 30));
+        assert_eq!(details, Rect::new(48, 1, 72, 4));
+        assert_eq!(logs, Rect::new(48, 5, 72, 26));
+        assert_eq!(narrow_list, Rect::new(0, 1, 80, 12));
+        assert_eq!(narrow_logs.top(), 17);
+    }
+
+    #[test]
+    fn build_the_hints_from_the_keymap() {
+        // given
+        let nodes = [
+            (InputAction::CordonNode, "to cordon"),
+            (InputAction::UncordonNode, "to uncordon"),
+            (InputAction::DrainNode, "to drain the node"),
+        ];
+        let file: KeymapFile =
+            toml::from_str("[nodes]\nx = \"cordon_node\"\nu = \"none\"").unwrap();
+        let rebound = Keymap::from_file(&file, false).unwrap();
+
+        // when
+        let default_hint = key_hint(&Keymap::defaults(false), &View::Nodes, &nodes);
+        let rebound_hint = key_hint(&rebound, &View::Nodes, &nodes);
+
+        // then
+        assert_eq!(
+            default_hint,
+            "Press 'c' to cordon, 'u' to uncordon or 'd' to drain the node."
+        );
+        assert_eq!(
+            rebound_hint,
+            "Press 'c' or 'x' to cordon, type ':uncordon_node' to uncordon or press 'd' to drain the node."
+        );
+    }
+
+    #[test]
+    fn keep_the_earlier_filter_when_its_edit_is_cancelled() {
+        // given
+        let mut app = App::new("default".to_owned(), None, None, None);
+        app.set_pod_filter(Some("api".to_owned()));
+        let mut ui = ui(&mut app);
+
+        // when
+        ui.open_pod_filter();
+        ui.handle_prompt_input(press(KeyCode::Backspace));
+        let while_editing = ui.app.pod_filter().map(|it| it.to_owned());
+        ui.handle_prompt_input(press(KeyCode::Esc));
+
+        // then
+        assert_eq!(while_editing.as_deref(), Some("ap"));
+        assert!(ui.prompt.is_none());
+        assert_eq!(ui.app.pod_filter(), Some("api"));
+    }
+
+    #[test]
+    fn run_only_the_actions_of_the_view() {
+        // given
+        let mut app = App::new("default".to_owned(), None, None, None);
+        let mut ui = ui(&mut app);
+
+        // when
+        let drain = ui.run_command(Command::Action(InputAction::DrainNode));
+        let logs = ui.run_command(Command::Action(InputAction::FetchLogs));
+        let deployments = ui.run_command(Command::Action(InputAction::ShowDeployments));
+
+        // then
+        assert_eq!(
+            drain.unwrap_err().to_string(),
+            "invalid command: drain_node isn't available in this view"
+        );
+        assert_eq!(logs.unwrap(), Some(InputAction::FetchLogs));
+        assert_eq!(deployments.unwrap(), Some(InputAction::ShowDeployments));
+    }
+
+    #[test]
+    fn keep_the_pending_confirmation_when_a_job_fails() {
+        // given
+        let mut app = App::new("default".to_owned(), None, None, None);
+        let app_id = app.id();
+        let mut ui = ui(&mut app);
+        ui.app.start_job(JobRequest::Deployments);
+        ui.popup = Some(Popup::confirm(
+            "Delete",
+            "pod/api-1",
+            Confirmation::DeleteResource("pod".to_owned(), "api-1".to_owned()),
+        ));
+
+        // when
+        ui.finish_job(JobResult::failed(app_id, JobKind::Deployments, 1, "boom"));
+
+        // then
+        assert!(ui.popup.as_ref().is_some_and(|it| it.is_confirmation()));
+        assert!(ui.status.is_some());
+    }
 }
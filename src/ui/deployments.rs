@@ -0,0 +1,70 @@
+use crate::deployment::Deployment;
+
+use super::{theme::Theme, widgets::header_cell};
+
+use tui::{
+    layout::Constraint,
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Cell, List, ListItem, Row, Table},
+};
+
+pub fn render_deployments_list<'a>(deployments: &'a [Deployment], theme: &Theme) -> List<'a> {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(theme.border())
+        .title("Deployments")
+        .border_type(BorderType::Plain);
+
+    let items: Vec<_> = deployments
+        .iter()
+        .map(|deployment| {
+            ListItem::new(Spans::from(vec![Span::styled(
+                deployment.name.as_str(),
+                Style::default(),
+            )]))
+        })
+        .collect();
+
+    List::new(items)
+        .block(block)
+        .highlight_style(theme.highlight())
+}
+
+pub fn render_deployment_details<'a>(selected: Option<&'a Deployment>, theme: &Theme) -> Table<'a> {
+    let rows = match selected {
+        Some(deployment) => vec![Row::new(vec![
+            Cell::from(Span::raw(deployment.name.as_str())),
+            Cell::from(Span::raw(deployment.ready.as_str())),
+            Cell::from(Span::raw(deployment.up_to_date.as_str())),
+            Cell::from(Span::raw(deployment.available.as_str())),
+            Cell::from(Span::raw(deployment.age.as_str())),
+        ])],
+        None => vec![],
+    };
+
+    let header_row = Row::new(vec![
+        header_cell("Name"),
+        header_cell("Ready"),
+        header_cell("Up-to-date"),
+        header_cell("Available"),
+        header_cell("Age"),
+    ]);
+
+    Table::new(rows)
+        .header(header_row)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(theme.border())
+                .title("Detail")
+                .border_type(BorderType::Plain),
+        )
+        .widths(&[
+            Constraint::Percentage(30),
+            Constraint::Percentage(10),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Percentage(10),
+        ])
+}
@@ -0,0 +1,72 @@
+use crate::cluster_event::ClusterEvent;
+
+use super::{theme::Theme, widgets::header_cell};
+
+use tui::{
+    layout::Constraint,
+    style::Style,
+    text::Span,
+    widgets::{Block, BorderType, Borders, Cell, Row, Table},
+};
+
+pub fn render_events<'a>(
+    events: &'a [ClusterEvent],
+    pod_name: Option<&str>,
+    theme: &Theme,
+) -> Table<'a> {
+    let title = match pod_name {
+        Some(pod_name) => format!("Events: pod/{pod_name}"),
+        None => "Events: namespace".to_string(),
+    };
+
+    let rows: Vec<_> = events
+        .iter()
+        .map(|event| {
+            Row::new(vec![
+                Cell::from(Span::raw(event.last_seen.as_str())),
+                Cell::from(Span::raw(event.event_type.as_str())),
+                Cell::from(Span::raw(event.reason.as_str())),
+                Cell::from(Span::raw(event.object.as_str())),
+                Cell::from(Span::raw(event.count.to_string())),
+                Cell::from(Span::raw(event.message.as_str())),
+            ])
+            .style(event_style(event, theme))
+        })
+        .collect();
+
+    let header_row = Row::new(vec![
+        header_cell("Last seen"),
+        header_cell("Type"),
+        header_cell("Reason"),
+        header_cell("Object"),
+        header_cell("Count"),
+        header_cell("Message"),
+    ]);
+
+    Table::new(rows)
+        .header(header_row)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(theme.border())
+                .title(title)
+                .border_type(BorderType::Plain),
+        )
+        .highlight_style(theme.highlight())
+        .widths(&[
+            Constraint::Length(20),
+            Constraint::Length(8),
+            Constraint::Length(18),
+            Constraint::Percentage(20),
+            Constraint::Length(6),
+            Constraint::Percentage(50),
+        ])
+}
+
+fn event_style(event: &ClusterEvent, theme: &Theme) -> Style {
+    if event.is_warning() {
+        theme.fg(theme.warning)
+    } else {
+        Style::default()
+    }
+}
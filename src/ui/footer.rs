@@ -1,35 +1,67 @@
+use crate::input::keymap::Keymap;
+
+use super::{theme::Theme, View};
 use tui::{
     layout::Alignment,
-    style::{Color, Style},
     widgets::{Block, BorderType, Borders, Paragraph},
 };
 
-pub fn render_about() -> Paragraph<'static> {
+pub fn render_about(theme: &Theme) -> Paragraph<'static> {
     let about = Paragraph::new("Kuber v0.1.0")
-        .style(Style::default().fg(Color::LightCyan))
+        .style(theme.fg(theme.info))
         .alignment(Alignment::Center)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White))
+                .style(theme.border())
                 .title("About")
                 .border_type(BorderType::Plain),
         );
     about
 }
 
-pub fn render_keys() -> Paragraph<'static> {
-    let about = Paragraph::new(
-        "Up/Down - select pod | Enter - fetch logs | PageUp/PageDown/End - scroll logs",
-    )
-    .style(Style::default().fg(Color::White))
-    .alignment(Alignment::Center)
-    .block(
-        Block::default()
-            .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White))
-            .title("Control")
-            .border_type(BorderType::Plain),
-    );
+/// Last failure of a background refresh, in place of the about box.
+pub fn render_status(message: &str, theme: &Theme) -> Paragraph<'static> {
+    Paragraph::new(message.to_owned())
+        .style(theme.fg(theme.error))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(theme.fg(theme.error))
+                .title("Status")
+                .border_type(BorderType::Plain),
+        )
+}
+
+/// Keys of the view generated from the keymap, switching views is left to the header.
+pub fn render_keys(view: &View, keymap: &Keymap, theme: &Theme) -> Paragraph<'static> {
+    let tabs_bindings = match View::TABS.contains(view) {
+        true => keymap.tabs_bindings(),
+        false => vec![],
+    };
+    let keys = keymap
+        .view_bindings(view)
+        .into_iter()
+        .chain(tabs_bindings.into_iter().filter(|(action, _)| {
+            !View::TABS
+                .iter()
+                .any(|it| it.show_action() == Some(*action))
+        }))
+        .map(|(action, chords)| {
+            let chords: Vec<_> = chords.iter().map(|it| it.to_string()).collect();
+            format!("{} - {}", chords.join("/"), action.description())
+        })
+        .collect::<Vec<_>>()
+        .join(" | ");
+    let about = Paragraph::new(keys)
+        .style(theme.text())
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(theme.border())
+                .title("Control")
+                .border_type(BorderType::Plain),
+        );
     about
 }
@@ -1,18 +1,108 @@
 use tui::{
-    style::{Color, Style},
-    widgets::{Block, BorderType, Borders, Paragraph},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph, Tabs},
 };
 
-pub fn render_info(namespace: &str) -> Paragraph<'static> {
-    let info = Paragraph::new(format!("Namespace: {namespace}"))
-        .style(Style::default().fg(Color::White))
-        // .alignment(Alignment::Center)
+use crate::{
+    app::App,
+    input::keymap::Keymap,
+    pod::{count_by_status, Pod, PodHealth},
+};
+
+use super::{
+    main_body::{health_style, health_symbol},
+    theme::Theme,
+    View,
+};
+
+pub fn render_info(
+    workspaces: &[String],
+    active_workspace: usize,
+    app: &App,
+    theme: &Theme,
+) -> Paragraph<'static> {
+    let mut text = format!("Namespace: {}", app.namespace());
+    if let Some(selector) = app.pod_selector() {
+        text.push_str(&format!(" | Selector: {selector}"));
+    }
+    if let Some(field_selector) = app.pod_field_selector() {
+        text.push_str(&format!(" | Field selector: {field_selector}"));
+    }
+    if let Some(filter) = app.pod_filter() {
+        text.push_str(&format!(" | Filter: {filter}"));
+    }
+    let info = Paragraph::new(vec![
+        Spans::from(text),
+        render_health_summary(app.all_pods(), theme),
+    ])
+    .style(theme.text())
+    // .alignment(Alignment::Center)
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .style(theme.border())
+            .title(render_workspaces(workspaces, active_workspace, theme))
+            .border_type(BorderType::Plain),
+    );
+    info
+}
+
+/// Tab bar of the workspaces, ex: `1: default  2: prod:kube-system`.
+fn render_workspaces(workspaces: &[String], active: usize, theme: &Theme) -> Spans<'static> {
+    let mut spans = vec![];
+    for (index, title) in workspaces.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::raw("  "));
+        }
+        let style = match index == active {
+            true => theme.title(),
+            false => theme.border(),
+        };
+        spans.push(Span::styled(format!("{}: {title}", index + 1), style));
+    }
+    Spans::from(spans)
+}
+
+/// Pods per status, ex: `Pods: ✖ CrashLoopBackOff 1  ● Running 12`.
+fn render_health_summary(pods: &[Pod], theme: &Theme) -> Spans<'static> {
+    let mut spans = vec![Span::raw(format!("Pods: {}", pods.len()))];
+    for (status, count) in count_by_status(pods) {
+        let health = PodHealth::of_status(status);
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("{} {status} {count}", health_symbol(health)),
+            health_style(health, theme),
+        ));
+    }
+    Spans::from(spans)
+}
+
+/// Tabs are titled with the first key switching to them.
+pub fn render_views(view: &View, keymap: &Keymap, theme: &Theme) -> Tabs<'static> {
+    let tabs_bindings = keymap.tabs_bindings();
+    let titles = View::TABS
+        .iter()
+        .map(|it| {
+            let chord = tabs_bindings
+                .iter()
+                .find(|(action, _)| Some(*action) == it.show_action())
+                .and_then(|(_, chords)| chords.first());
+            match chord {
+                Some(chord) => Spans::from(format!("{chord} {}", it.title())),
+                None => Spans::from(it.title()),
+            }
+        })
+        .collect();
+
+    Tabs::new(titles)
+        .select(view.tab_index())
+        .style(theme.text())
+        .highlight_style(theme.title())
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White))
-                .title("Base info")
+                .style(theme.border())
+                .title("Views")
                 .border_type(BorderType::Plain),
-        );
-    info
+        )
 }
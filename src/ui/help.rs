@@ -0,0 +1,195 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+
+use crate::input::{keymap::Keymap, ActionCategory, InputAction};
+
+use super::{theme::Theme, View};
+
+pub enum HelpResult {
+    Pending,
+    Closed,
+}
+
+/// Every action available in the view with its keys, grouped by category and narrowed by typing.
+pub struct Help {
+    view: View,
+    entries: Vec<(InputAction, String)>,
+    query: String,
+    scroll: u16,
+}
+
+impl Help {
+    pub fn new(view: View, keymap: &Keymap) -> Help {
+        let tabs_bindings = match View::TABS.contains(&view) {
+            true => keymap.tabs_bindings(),
+            false => vec![],
+        };
+        let mut entries: Vec<_> = keymap
+            .view_bindings(&view)
+            .into_iter()
+            .chain(tabs_bindings)
+            .map(|(action, chords)| {
+                let chords: Vec<_> = chords.iter().map(|it| it.to_string()).collect();
+                (action, chords.join(", "))
+            })
+            .collect();
+        // stable, so the actions keep the keymap order inside the categories
+        entries.sort_by_key(|(action, _)| action.category());
+
+        Help {
+            view,
+            entries,
+            query: String::new(),
+            scroll: 0,
+        }
+    }
+
+    /// Esc clears the search first, and closes the help when there is none.
+    pub fn handle_key(&mut self, key: KeyEvent) -> HelpResult {
+        match key.code {
+            KeyCode::Esc if self.query.is_empty() => return HelpResult::Closed,
+            KeyCode::Esc => self.query.clear(),
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.scroll = 0;
+            }
+            KeyCode::Char(c) => {
+                self.query.push(c);
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+        HelpResult::Pending
+    }
+
+    pub fn render(&self, theme: &Theme) -> Paragraph<'static> {
+        let mut lines = vec![];
+        let mut category: Option<ActionCategory> = None;
+        for (action, chords) in self.matching_entries() {
+            if category != Some(action.category()) {
+                if category.is_some() {
+                    lines.push(Spans::default());
+                }
+                category = Some(action.category());
+                lines.push(Spans::from(Span::styled(
+                    action.category().title(),
+                    theme.title(),
+                )));
+            }
+            lines.push(Spans::from(vec![
+                Span::styled(format!("  {chords:<20}"), theme.fg(theme.info)),
+                Span::raw(format!("{:<24}", action.description())),
+                Span::styled(action.name(), theme.fg(theme.muted)),
+            ]));
+        }
+        if lines.is_empty() {
+            lines.push(Spans::from("No matching actions."));
+        }
+
+        Paragraph::new(lines)
+            .style(theme.text())
+            .scroll((self.scroll, 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .style(theme.overlay_border())
+                    .title(format!(
+                        "Help: {} - search: {}_ (Up/Down - scroll, Esc - close)",
+                        self.view.title(),
+                        self.query
+                    ))
+                    .border_type(BorderType::Double),
+            )
+    }
+
+    /// Searches the keys, descriptions, keymap names and categories.
+    fn matching_entries(&self) -> Vec<&(InputAction, String)> {
+        let query = self.query.to_lowercase();
+        self.entries
+            .iter()
+            .filter(|(action, chords)| {
+                [
+                    chords.as_str(),
+                    action.description(),
+                    action.name(),
+                    action.category().title(),
+                ]
+                .iter()
+                .any(|it| it.to_lowercase().contains(&query))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyModifiers;
+
+    use super::*;
+
+    fn press(help: &mut Help, code: KeyCode) -> HelpResult {
+        help.handle_key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn group_the_actions_by_category() {
+        // when
+        let help = Help::new(View::Pods, &Keymap::defaults(false));
+
+        // then
+        let categories: Vec<_> = help
+            .entries
+            .iter()
+            .map(|(action, _)| action.category())
+            .collect();
+        let mut sorted = categories.clone();
+        sorted.sort();
+        assert_eq!(categories, sorted);
+        assert!(help
+            .entries
+            .iter()
+            .any(|(action, _)| *action == InputAction::Quit));
+    }
+
+    #[test]
+    fn search_the_descriptions_and_names() {
+        // given
+        let mut help = Help::new(View::Pods, &Keymap::defaults(false));
+
+        // when
+        for c in "fetch_lo".chars() {
+            press(&mut help, KeyCode::Char(c));
+        }
+
+        // then
+        let matching: Vec<_> = help
+            .matching_entries()
+            .iter()
+            .map(|(action, _)| *action)
+            .collect();
+        assert_eq!(matching, vec![InputAction::FetchLogs]);
+    }
+
+    #[test]
+    fn clear_the_search_before_closing() {
+        // given
+        let mut help = Help::new(View::Pods, &Keymap::defaults(false));
+        press(&mut help, KeyCode::Char('x'));
+
+        // when
+        let first = press(&mut help, KeyCode::Esc);
+        let second = press(&mut help, KeyCode::Esc);
+
+        // then
+        assert!(matches!(first, HelpResult::Pending));
+        assert!(help.query.is_empty());
+        assert!(matches!(second, HelpResult::Closed));
+    }
+}
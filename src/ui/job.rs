@@ -0,0 +1,174 @@
+use std::{thread, time::Instant};
+
+use tui::{backend::Backend, layout::Rect, widgets::Paragraph, Frame};
+
+use crate::app::{Job, JobKind, JobOutcome, JobRequest, JobResult};
+
+use super::{apply_popup, manifest::ManifestWidget, popup::Popup, review_popup, View, UI};
+
+/// Events posted by the background workers into the event loop.
+pub enum WorkerEvent {
+    JobDone(JobResult),
+}
+
+/// Frames of the spinner shown on a pane while its job runs, one per tick.
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+impl UI<'_> {
+    /// Keeps the events live while they are shown.
+    pub(super) fn refresh_events_if_due(&mut self) {
+        if self.view != View::Events
+            || self.app.is_loading(JobKind::Events)
+            || self.events_refreshed_at.elapsed() < self.config.events_refresh_interval()
+        {
+            return;
+        }
+
+        self.start_job(JobRequest::Events);
+        self.events_refreshed_at = Instant::now();
+    }
+
+    /// Collects the usage history of the pods while they are shown.
+    pub(super) fn refresh_metrics_if_due(&mut self) {
+        let due = self
+            .metrics_refreshed_at
+            .is_none_or(|it| it.elapsed() >= self.config.metrics_refresh_interval());
+        if self.view != View::Pods || self.app.is_loading(JobKind::Metrics) || !due {
+            return;
+        }
+
+        self.start_job(JobRequest::Metrics);
+        self.metrics_refreshed_at = Some(Instant::now());
+    }
+
+    /// Runs the job on a worker thread, its result comes back as an event.
+    pub(super) fn start_job(&mut self, request: JobRequest) {
+        let job = self.app.start_job(request);
+        self.spawn_job(job);
+    }
+
+    fn spawn_job(&self, job: Job) {
+        let sender = self.event_loop.sender();
+        thread::spawn(move || {
+            // Nobody is left to apply the result once the loop is shut down
+            sender.send(WorkerEvent::JobDone(job.run()));
+        });
+    }
+
+    /// Results go to the workspace which started the job, and are dropped when it's closed.
+    pub(super) fn finish_job(&mut self, result: JobResult) {
+        if result.app_id() != self.app.id() {
+            self.finish_background_job(result);
+            return;
+        }
+
+        let kind = result.kind();
+        let failure_title = result.failure_title();
+        match self.app.finish_job(result) {
+            Ok(JobOutcome::Done) => {}
+            Ok(JobOutcome::Then(request)) => self.start_job(request),
+            Ok(JobOutcome::Reviewed(path, diff)) => self.popup = Some(review_popup(path, diff)),
+            Ok(JobOutcome::Applied(path, output)) => {
+                if output.is_ok() {
+                    self.manifest_widget = ManifestWidget::new();
+                }
+                self.popup = Some(apply_popup(&path, output));
+            }
+            Err(err) if reported_in_status(kind) => self.report_status(&failure_title, &err),
+            Err(err) => self.report_error(&failure_title, &err),
+        }
+
+        match kind {
+            JobKind::Pods => {
+                self.selected_pod_index = self
+                    .selected_pod_index
+                    .min(self.app.get_pods_number().saturating_sub(1))
+            }
+            JobKind::Events => {
+                self.selected_event_index = self
+                    .selected_event_index
+                    .min(self.app.get_events_number().saturating_sub(1))
+            }
+            JobKind::Manifest if self.view == View::Manifest && self.app.manifest().is_none() => {
+                self.view = self.previous_view
+            }
+            // Once loaded, so an empty list doesn't start the job over
+            JobKind::ResourceKinds
+                if self.view == View::Resources && !self.app.resource_kinds().is_empty() =>
+            {
+                self.open_resource_kind_picker()
+            }
+            JobKind::Namespaces if !self.app.namespaces().is_empty() => self.complete_command(),
+            JobKind::Contexts if !self.app.contexts().is_empty() => self.complete_command(),
+            _ => {}
+        }
+    }
+
+    /// Popups and the status of the workspace wait until it's back in the foreground.
+    fn finish_background_job(&mut self, result: JobResult) {
+        let Some(index) = self
+            .workspaces
+            .iter()
+            .position(|it| it.app.id() == result.app_id())
+        else {
+            return;
+        };
+
+        let kind = result.kind();
+        let failure_title = result.failure_title();
+        let workspace = &mut self.workspaces[index];
+        let popup = match workspace.app.finish_job(result) {
+            Ok(JobOutcome::Done) => None,
+            Ok(JobOutcome::Then(request)) => {
+                let job = workspace.app.start_job(request);
+                self.spawn_job(job);
+                None
+            }
+            Ok(JobOutcome::Reviewed(path, diff)) => Some(review_popup(path, diff)),
+            Ok(JobOutcome::Applied(path, output)) => {
+                if output.is_ok() {
+                    workspace.manifest_widget = ManifestWidget::new();
+                }
+                Some(apply_popup(&path, output))
+            }
+            Err(err) if reported_in_status(kind) => {
+                workspace.status = Some((format!("{failure_title}: {err}"), Instant::now()));
+                None
+            }
+            Err(err)
+                if workspace
+                    .popup
+                    .as_ref()
+                    .is_some_and(|it| it.is_confirmation()) =>
+            {
+                workspace.status = Some((format!("{failure_title}: {err}"), Instant::now()));
+                None
+            }
+            Err(err) => Some(Popup::error(&failure_title, &err.to_string())),
+        };
+        if popup.is_some() {
+            self.workspaces[index].popup = popup;
+        }
+    }
+
+    /// Spinner on the top right border of the pane, while its job runs.
+    pub(super) fn draw_spinner<B>(&self, rect: &mut Frame<B>, chunk: Rect, kind: JobKind)
+    where
+        B: Backend,
+    {
+        if !self.app.is_loading(kind) || chunk.width < 8 {
+            return;
+        }
+        let frame = SPINNER[self.spinner_frame % SPINNER.len()];
+        let spinner = Paragraph::new(format!(" {frame} ")).style(self.theme.title());
+        rect.render_widget(spinner, Rect::new(chunk.right() - 5, chunk.y, 3, 1));
+    }
+}
+
+/// Failures of the background refreshes and of the completion don't interrupt.
+fn reported_in_status(kind: JobKind) -> bool {
+    matches!(
+        kind,
+        JobKind::Metrics | JobKind::Events | JobKind::Namespaces | JobKind::Contexts
+    )
+}
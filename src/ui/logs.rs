@@ -1,20 +1,25 @@
 use tui::{
     layout::Corner::BottomLeft,
-    style::{Color, Style},
+    style::Style,
     text::{Span, Spans},
     widgets::{Block, BorderType, Borders, List, ListItem},
 };
 
+use super::{theme::Theme, widgets::page_size};
+
 pub struct LoggerWidget {
     pod_name: Option<String>,
     offset: usize,
+    /// How many of the most recent lines are shown.
+    lines: usize,
 }
 
 impl LoggerWidget {
-    pub fn new(pod_name: Option<String>) -> LoggerWidget {
+    pub fn new(pod_name: Option<String>, lines: usize) -> LoggerWidget {
         LoggerWidget {
             pod_name,
             offset: 0,
+            lines,
         }
     }
 
@@ -22,15 +27,20 @@ impl LoggerWidget {
         self.pod_name != actual_logged_pod_name
     }
 
-    pub fn page_up(&mut self, _chunk_height: u16) {
+    pub fn line_up(&mut self) {
         self.offset += 1
     }
 
-    pub fn page_down(&mut self, _chunk_height: u16) {
-        //temp
-        if self.offset != 0 {
-            self.offset -= 1
-        }
+    pub fn line_down(&mut self) {
+        self.offset = self.offset.saturating_sub(1)
+    }
+
+    pub fn page_up(&mut self, chunk_height: u16) {
+        self.offset += page_size(chunk_height)
+    }
+
+    pub fn page_down(&mut self, chunk_height: u16) {
+        self.offset = self.offset.saturating_sub(page_size(chunk_height))
     }
 
     pub fn end(&mut self) {
@@ -40,11 +50,15 @@ impl LoggerWidget {
     pub fn render_pod_logs<'a>(
         &self,
         logs_opt: Option<&'a Vec<String>>,
+        placeholder: &'a str,
         chunk_width: &u16,
+        focused: bool,
+        theme: &Theme,
     ) -> List<'a> {
         let pods = Block::default()
             .borders(Borders::ALL)
-            .style(Style::default().fg(Color::White))
+            .style(theme.border())
+            .border_style(theme.pane_border(focused))
             .title(match &self.pod_name {
                 Some(pod_name) => format!("Logs: {pod_name}"),
                 None => "Logs".to_string(),
@@ -56,17 +70,14 @@ impl LoggerWidget {
                 let length = logs.len();
                 let log_list: Vec<_> = logs
                     .iter()
-                    .take(length - self.offset)
+                    .take(length.saturating_sub(self.offset))
                     .rev()
-                    .take(50 + self.offset)
-                    .map(|it| default_list_item(it, chunk_width))
+                    .take(self.lines + self.offset)
+                    .map(|it| default_list_item(it, chunk_width, theme))
                     .collect();
                 log_list
             }
-            None => vec![default_list_item(
-                "Press 'Enter' to load pod logs.",
-                chunk_width,
-            )],
+            None => vec![default_list_item(placeholder, chunk_width, theme)],
         };
 
         let list = List::new(items).block(pods).start_corner(BottomLeft);
@@ -74,27 +85,26 @@ impl LoggerWidget {
     }
 }
 
-fn default_list_item<'a>(value: &'a str, chunk_width: &u16) -> ListItem<'a> {
+fn default_list_item<'a>(value: &'a str, chunk_width: &u16, theme: &Theme) -> ListItem<'a> {
     let spans: Vec<_> = textwrap::wrap(value, *chunk_width as usize)
         .into_iter()
         .map(|it| {
-            let style = choose_style(&it);
+            let style = choose_style(&it, theme);
             Span::styled(it, style)
         })
-        .map(|it| Spans::from(it))
+        .map(Spans::from)
         .collect();
 
     ListItem::new(spans)
 }
 
-fn choose_style(log_line: &str) -> Style {
-    let st = Style::default();
+fn choose_style(log_line: &str, theme: &Theme) -> Style {
     match log_line {
-        s if s.contains("FATAL") => st.fg(Color::Red),
-        s if s.contains("ERROR") => st.fg(Color::Red),
-        s if s.contains("WARN") => st.fg(Color::LightYellow),
-        s if s.contains("INFO") => st.fg(Color::White),
-        s if s.contains("DEBUG") => st.fg(Color::Gray),
-        _ => st,
+        s if s.contains("FATAL") => theme.fg(theme.error),
+        s if s.contains("ERROR") => theme.fg(theme.error),
+        s if s.contains("WARN") => theme.fg(theme.warning),
+        s if s.contains("INFO") => theme.text(),
+        s if s.contains("DEBUG") => theme.fg(theme.secondary),
+        _ => Style::default(),
     }
 }
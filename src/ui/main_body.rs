@@ -1,47 +1,173 @@
-use crate::pod::Pod;
+use crate::{
+    app::CommandOutput,
+    fuzzy::FuzzyMatch,
+    metrics::{format_cpu, format_memory, percent, PodMetrics},
+    pod::{Pod, PodColumn, PodHealth},
+};
+
+use super::{theme::Theme, widgets::header_cell};
 
 use tui::{
     layout::Constraint,
-    style::{Color, Modifier, Style},
+    style::{Modifier, Style},
     text::{Span, Spans},
-    widgets::{Block, BorderType, Borders, Cell, List, ListItem, Row, Table},
+    widgets::{Block, BorderType, Borders, Cell, Paragraph, Row, Table},
 };
 
-pub fn render_pods_list<'a>(pod_list: &'a Vec<Pod>) -> List<'a> {
+/// Characters matched by the filter are highlighted, the title tells how many pods are hidden.
+/// Columns are as wide as their longest value, so the name is never cut when it fits.
+pub fn render_pods_table<'a>(
+    pod_list: Vec<(&'a Pod, &'a FuzzyMatch)>,
+    total: usize,
+    columns: &[PodColumn],
+    sort: (PodColumn, bool),
+    focused: bool,
+    theme: &Theme,
+) -> (Table<'a>, Vec<Constraint>) {
+    let title = match pod_list.len() {
+        shown if shown == total => "Pods".to_owned(),
+        shown => format!("Pods ({shown}/{total})"),
+    };
     let pods = Block::default()
         .borders(Borders::ALL)
-        .style(Style::default().fg(Color::White))
-        .title("Pods")
+        .style(theme.border())
+        .border_style(theme.pane_border(focused))
+        .title(title)
         .border_type(BorderType::Plain);
 
-    let items: Vec<_> = pod_list
+    let widths = columns
         .iter()
-        .map(|pod| {
-            ListItem::new(Spans::from(vec![Span::styled(
-                pod.name.clone(),
-                Style::default(),
-            )]))
+        .map(|column| {
+            let longest = pod_list
+                .iter()
+                .map(|(pod, _)| cell_text(column, pod).chars().count())
+                .max()
+                .unwrap_or(0);
+            Constraint::Length((longest.max(column.title().len() + 2)) as u16)
         })
         .collect();
 
-    let list = List::new(items).block(pods).highlight_style(
-        Style::default()
-            .bg(Color::Yellow)
-            .fg(Color::Black)
-            .add_modifier(Modifier::BOLD),
+    let header_row = Row::new(
+        columns
+            .iter()
+            .map(|column| match sort {
+                (sorted, false) if sorted == *column => format!("{} ▲", column.title()),
+                (sorted, true) if sorted == *column => format!("{} ▼", column.title()),
+                _ => column.title().to_owned(),
+            })
+            .map(header_cell)
+            .collect::<Vec<_>>(),
     );
 
-    list
+    let rows: Vec<_> = pod_list
+        .into_iter()
+        .map(|(pod, matched)| {
+            let row = Row::new(
+                columns
+                    .iter()
+                    .map(|column| match column {
+                        PodColumn::Name => Cell::from(highlight_matches(&pod.name, matched, theme)),
+                        PodColumn::Status => Cell::from(Span::styled(
+                            cell_text(column, pod),
+                            health_style(pod.health(), theme),
+                        )),
+                        PodColumn::Ready if pod.is_not_ready() => Cell::from(Span::styled(
+                            cell_text(column, pod),
+                            theme.fg(theme.matched).add_modifier(Modifier::BOLD),
+                        )),
+                        column => Cell::from(cell_text(column, pod)),
+                    })
+                    .collect::<Vec<_>>(),
+            );
+            match pod.health() {
+                PodHealth::Terminating => row.style(health_style(PodHealth::Terminating, theme)),
+                _ => row,
+            }
+        })
+        .collect();
+
+    let table = Table::new(rows)
+        .header(header_row)
+        .block(pods)
+        .column_spacing(2)
+        .highlight_style(theme.highlight());
+
+    (table, widths)
+}
+
+/// Status goes with a symbol, so the state is visible without colours too.
+fn cell_text(column: &PodColumn, pod: &Pod) -> String {
+    match column {
+        PodColumn::Status => format!("{} {}", health_symbol(pod.health()), pod.status),
+        column => column.value(pod),
+    }
+}
+
+pub fn health_symbol(health: PodHealth) -> &'static str {
+    match health {
+        PodHealth::Healthy => "●",
+        PodHealth::Pending => "◐",
+        PodHealth::Failing => "✖",
+        PodHealth::Terminating => "◌",
+        PodHealth::Unknown => "?",
+    }
 }
 
-pub fn render_pod_details<'a>(selected_pod: Pod) -> Table<'a> {
-    let rows = vec![Row::new(vec![
-        Cell::from(Span::raw(selected_pod.name)),
-        Cell::from(Span::raw(selected_pod.ready)),
-        Cell::from(Span::raw(selected_pod.status)),
-        Cell::from(Span::raw(selected_pod.restarts)),
-        Cell::from(Span::raw(selected_pod.age)),
-    ])];
+pub fn health_style(health: PodHealth, theme: &Theme) -> Style {
+    match health {
+        PodHealth::Healthy => theme.fg(theme.good),
+        PodHealth::Pending => theme.fg(theme.warning),
+        PodHealth::Failing => theme.fg(theme.error),
+        PodHealth::Terminating => theme.fg(theme.muted).add_modifier(Modifier::DIM),
+        PodHealth::Unknown => Style::default(),
+    }
+}
+
+fn highlight_matches<'a>(text: &str, matched: &FuzzyMatch, theme: &Theme) -> Spans<'a> {
+    let matched_style = theme.fg(theme.matched).add_modifier(Modifier::BOLD);
+
+    Spans::from(
+        text.chars()
+            .enumerate()
+            .map(|(position, c)| match matched.is_matched(position) {
+                true => Span::styled(c.to_string(), matched_style),
+                false => Span::raw(c.to_string()),
+            })
+            .collect::<Vec<_>>(),
+    )
+}
+
+pub fn render_pod_details<'a>(
+    selected_pod: Option<Pod>,
+    pod_metrics: Option<&PodMetrics>,
+    focused: bool,
+    theme: &Theme,
+) -> Table<'a> {
+    let rows = match selected_pod {
+        Some(selected_pod) => {
+            let pod_metrics = pod_metrics.cloned().unwrap_or_default();
+            vec![Row::new(vec![
+                Cell::from(Span::raw(selected_pod.name.clone())),
+                Cell::from(Span::raw(selected_pod.ready.to_string())),
+                Cell::from(Span::raw(selected_pod.status.clone())),
+                Cell::from(Span::raw(selected_pod.restarts_description())),
+                Cell::from(Span::raw(selected_pod.age_description())),
+                Cell::from(Span::raw(usage(pod_metrics.cpu, format_cpu))),
+                Cell::from(Span::raw(percents(
+                    pod_metrics.cpu,
+                    pod_metrics.cpu_request,
+                    pod_metrics.cpu_limit,
+                ))),
+                Cell::from(Span::raw(usage(pod_metrics.memory, format_memory))),
+                Cell::from(Span::raw(percents(
+                    pod_metrics.memory,
+                    pod_metrics.memory_request,
+                    pod_metrics.memory_limit,
+                ))),
+            ])]
+        }
+        None => vec![],
+    };
 
     let header_row = Row::new(vec![
         header_cell("Name"),
@@ -49,6 +175,10 @@ pub fn render_pod_details<'a>(selected_pod: Pod) -> Table<'a> {
         header_cell("Status"),
         header_cell("Restarts"),
         header_cell("Age"),
+        header_cell("CPU"),
+        header_cell("Req/Lim"),
+        header_cell("Memory"),
+        header_cell("Req/Lim"),
     ]);
 
     let pod_detail = Table::new(rows)
@@ -56,24 +186,66 @@ pub fn render_pod_details<'a>(selected_pod: Pod) -> Table<'a> {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .style(Style::default().fg(Color::White))
+                .style(theme.border())
+                .border_style(theme.pane_border(focused))
                 .title("Detail")
                 .border_type(BorderType::Plain),
         )
         .widths(&[
-            Constraint::Percentage(30),
+            Constraint::Percentage(25),
+            Constraint::Percentage(7),
+            Constraint::Percentage(12),
+            Constraint::Percentage(10),
+            Constraint::Percentage(6),
+            Constraint::Percentage(8),
             Constraint::Percentage(10),
-            Constraint::Percentage(15),
-            Constraint::Percentage(15),
+            Constraint::Percentage(8),
             Constraint::Percentage(10),
         ]);
 
     pod_detail
 }
 
-fn header_cell(title: &str) -> Cell {
-    Cell::from(Span::styled(
-        title,
-        Style::default().add_modifier(Modifier::BOLD),
-    ))
+fn usage(value: Option<u64>, format: fn(u64) -> String) -> String {
+    value.map(format).unwrap_or_else(|| "-".to_owned())
+}
+
+/// Usage as a percentage of the requests and of the limits, ex: `60%/30%`.
+fn percents(value: Option<u64>, request: Option<u64>, limit: Option<u64>) -> String {
+    let format = |percent: Option<u64>| match percent {
+        Some(percent) => format!("{percent}%"),
+        None => "-".to_owned(),
+    };
+    format!(
+        "{}/{}",
+        format(percent(value, request)),
+        format(percent(value, limit))
+    )
+}
+
+/// Output of the last command, or the hint how to run one.
+pub fn render_command_output<'a>(
+    output: Option<&'a CommandOutput>,
+    placeholder: &'a str,
+    theme: &Theme,
+) -> Paragraph<'a> {
+    let (title, text): (&str, Vec<_>) = match output {
+        Some(output) => (
+            output.title(),
+            output
+                .lines()
+                .iter()
+                .map(|it| Spans::from(it.as_str()))
+                .collect(),
+        ),
+        None => ("Output", vec![Spans::from(placeholder)]),
+    };
+
+    Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .style(theme.border())
+            .title(title)
+            .border_type(BorderType::Plain),
+    )
 }
@@ -0,0 +1,247 @@
+use std::collections::HashSet;
+
+use tui::{
+    style::{Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, List, ListItem},
+};
+
+use crate::manifest::Manifest;
+
+use super::{theme::Theme, widgets::page_size};
+
+pub struct ManifestWidget {
+    cursor: usize,
+    offset: usize,
+    folded: HashSet<usize>,
+    hide_noise: bool,
+    query: Option<String>,
+}
+
+impl ManifestWidget {
+    pub fn new() -> ManifestWidget {
+        ManifestWidget {
+            cursor: 0,
+            offset: 0,
+            folded: HashSet::new(),
+            hide_noise: false,
+            query: None,
+        }
+    }
+
+    pub fn up(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn down(&mut self, manifest: &Manifest) {
+        let last = self.visible_lines(manifest).len().saturating_sub(1);
+        self.cursor = (self.cursor + 1).min(last);
+    }
+
+    pub fn page_up(&mut self, chunk_height: u16) {
+        self.cursor = self.cursor.saturating_sub(page_size(chunk_height));
+    }
+
+    pub fn page_down(&mut self, manifest: &Manifest, chunk_height: u16) {
+        let last = self.visible_lines(manifest).len().saturating_sub(1);
+        self.cursor = (self.cursor + page_size(chunk_height)).min(last);
+    }
+
+    pub fn toggle_fold(&mut self, manifest: &Manifest) {
+        if let Some(&line) = self.visible_lines(manifest).get(self.cursor) {
+            if !self.folded.remove(&line) && manifest.is_foldable(line) {
+                self.folded.insert(line);
+            }
+        }
+    }
+
+    pub fn toggle_noise(&mut self, manifest: &Manifest) {
+        let current_line = self.visible_lines(manifest).get(self.cursor).copied();
+        self.hide_noise = !self.hide_noise;
+
+        if let Some(line) = current_line {
+            self.move_cursor_to(manifest, line);
+        }
+    }
+
+    pub fn search(&mut self, query: String, manifest: &Manifest) {
+        self.query = if query.is_empty() { None } else { Some(query) };
+
+        let visible = self.visible_lines(manifest);
+        if let Some(position) = (self.cursor..visible.len())
+            .chain(0..self.cursor)
+            .find(|it| self.is_match(manifest, visible[*it]))
+        {
+            self.cursor = position;
+        }
+    }
+
+    pub fn next_match(&mut self, manifest: &Manifest) {
+        let visible = self.visible_lines(manifest);
+        if let Some(position) = (self.cursor + 1..visible.len())
+            .chain(0..=self.cursor)
+            .find(|it| self.is_match(manifest, visible[*it]))
+        {
+            self.cursor = position;
+        }
+    }
+
+    pub fn previous_match(&mut self, manifest: &Manifest) {
+        let visible = self.visible_lines(manifest);
+        if let Some(position) = (0..self.cursor)
+            .rev()
+            .chain((self.cursor..visible.len()).rev())
+            .find(|it| self.is_match(manifest, visible[*it]))
+        {
+            self.cursor = position;
+        }
+    }
+
+    pub fn render_manifest<'a>(
+        &mut self,
+        manifest: &'a Manifest,
+        chunk_height: u16,
+        theme: &Theme,
+    ) -> List<'a> {
+        let visible = self.visible_lines(manifest);
+        let height = page_size(chunk_height);
+        self.cursor = self.cursor.min(visible.len().saturating_sub(1));
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + height {
+            self.offset = self.cursor + 1 - height;
+        }
+
+        let items: Vec<_> = visible
+            .iter()
+            .enumerate()
+            .skip(self.offset)
+            .take(height)
+            .map(|(position, line)| self.list_item(manifest, *line, position == self.cursor, theme))
+            .collect();
+
+        List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(theme.border())
+                .title(self.title(manifest, &visible))
+                .border_type(BorderType::Plain),
+        )
+    }
+
+    fn title(&self, manifest: &Manifest, visible: &[usize]) -> String {
+        let mut title = format!("Manifest: {}/{}", manifest.kind(), manifest.name());
+        if self.hide_noise {
+            title.push_str(" [managedFields and status hidden]");
+        }
+        if let Some(query) = &self.query {
+            let matches = visible
+                .iter()
+                .filter(|it| manifest.matches(**it, query))
+                .count();
+            title.push_str(&format!(" [/{query}: {matches} matches]"));
+        }
+        title
+    }
+
+    fn list_item<'a>(
+        &self,
+        manifest: &'a Manifest,
+        line: usize,
+        selected: bool,
+        theme: &Theme,
+    ) -> ListItem<'a> {
+        let gutter = match (manifest.is_foldable(line), self.folded.contains(&line)) {
+            (true, true) => "▸ ",
+            (true, false) => "▾ ",
+            _ => "  ",
+        };
+
+        let mut spans = vec![Span::styled(gutter, theme.fg(theme.muted))];
+        spans.extend(highlight_line(manifest.line(line), theme));
+        if self.folded.contains(&line) {
+            spans.push(Span::styled(" …", theme.fg(theme.muted)));
+        }
+
+        let background = if selected {
+            Some(theme.selected_line())
+        } else if self.is_match(manifest, line) {
+            Some(theme.search_match())
+        } else {
+            None
+        };
+        if let Some(background) = background {
+            spans
+                .iter_mut()
+                .for_each(|it| it.style = it.style.patch(background));
+        }
+
+        ListItem::new(Spans::from(spans))
+    }
+
+    fn visible_lines(&self, manifest: &Manifest) -> Vec<usize> {
+        manifest.visible_lines(&self.folded, self.hide_noise)
+    }
+
+    fn is_match(&self, manifest: &Manifest, line: usize) -> bool {
+        match &self.query {
+            Some(query) => manifest.matches(line, query),
+            None => false,
+        }
+    }
+
+    fn move_cursor_to(&mut self, manifest: &Manifest, line: usize) {
+        let visible = self.visible_lines(manifest);
+        self.cursor = visible.iter().rposition(|it| *it <= line).unwrap_or(0);
+    }
+}
+
+fn highlight_line<'a>(line: &'a str, theme: &Theme) -> Vec<Span<'a>> {
+    let content = line.trim_start();
+    let mut spans = vec![Span::raw(&line[..line.len() - content.len()])];
+
+    if content.starts_with('#') {
+        spans.push(Span::styled(content, theme.fg(theme.muted)));
+        return spans;
+    }
+
+    let mut rest = content;
+    if let Some(item) = rest.strip_prefix("- ") {
+        spans.push(Span::styled("- ", theme.fg(theme.accent)));
+        rest = item;
+    }
+
+    match split_key(rest) {
+        Some((key, value)) => {
+            spans.push(Span::styled(
+                key,
+                theme.fg(theme.key).add_modifier(Modifier::BOLD),
+            ));
+            spans.push(Span::raw(":"));
+            spans.push(Span::styled(value, value_style(value.trim(), theme)));
+        }
+        None => spans.push(Span::styled(rest, value_style(rest, theme))),
+    }
+
+    spans
+}
+
+fn split_key(entry: &str) -> Option<(&str, &str)> {
+    if entry.starts_with('"') || entry.starts_with('\'') {
+        return None;
+    }
+
+    match entry.find(": ") {
+        Some(position) => Some((&entry[..position], &entry[position + 1..])),
+        None => entry.strip_suffix(':').map(|key| (key, "")),
+    }
+}
+
+fn value_style(value: &str, theme: &Theme) -> Style {
+    match value {
+        "true" | "false" | "null" | "~" => theme.fg(theme.literal),
+        "|" | "|-" | "|+" | ">" | ">-" | ">+" => theme.fg(theme.muted),
+        v if v.parse::<f64>().is_ok() => theme.fg(theme.literal),
+        _ => theme.fg(theme.good),
+    }
+}
@@ -0,0 +1,42 @@
+use crate::metrics::{format_cpu, format_memory};
+
+use super::theme::Theme;
+
+use tui::{
+    style::Color,
+    widgets::{Block, BorderType, Borders, Sparkline},
+};
+
+pub fn render_cpu_history<'a>(history: &'a [u64], theme: &Theme) -> Sparkline<'a> {
+    let title = match history.last() {
+        Some(cpu) => format!("CPU {}", format_cpu(*cpu)),
+        None => "CPU".to_owned(),
+    };
+    render_history(title, history, theme.key, theme)
+}
+
+pub fn render_memory_history<'a>(history: &'a [u64], theme: &Theme) -> Sparkline<'a> {
+    let title = match history.last() {
+        Some(memory) => format!("Memory {}", format_memory(*memory)),
+        None => "Memory".to_owned(),
+    };
+    render_history(title, history, theme.literal, theme)
+}
+
+fn render_history<'a>(
+    title: String,
+    history: &'a [u64],
+    color: Color,
+    theme: &Theme,
+) -> Sparkline<'a> {
+    Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(theme.border())
+                .title(title)
+                .border_type(BorderType::Plain),
+        )
+        .style(theme.fg(color))
+        .data(history)
+}
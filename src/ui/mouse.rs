@@ -0,0 +1,189 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use tui::layout::Rect;
+
+use crate::input::InputAction;
+
+use super::{widgets::table_page_size, Pane, UiContext, View, UI};
+
+/// Two clicks on the same pod within it fetch its logs.
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(500);
+
+impl UI<'_> {
+    /// Clicks focus the pane under the cursor, the wheel scrolls the logs or browses the pods.
+    pub(super) fn handle_mouse(&mut self, mouse: MouseEvent, context: &UiContext) {
+        if self.view != View::Pods {
+            return;
+        }
+        let Some(pane) = context.pane_at(mouse.column, mouse.row) else {
+            return;
+        };
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.focus = pane;
+                if pane == Pane::PodList {
+                    self.click_pod(mouse.row, context);
+                }
+            }
+            MouseEventKind::ScrollUp if pane == Pane::Logs => self.logger_widget.line_up(),
+            MouseEventKind::ScrollDown if pane == Pane::Logs => self.logger_widget.line_down(),
+            MouseEventKind::ScrollUp => self.select_previous_pod(),
+            MouseEventKind::ScrollDown => self.select_next_pod(),
+            _ => {}
+        }
+    }
+
+    fn click_pod(&mut self, row: u16, context: &UiContext) {
+        let pods_number = self.app.get_pods_number();
+        let Some(index) = pod_at_row(row, context.pods_chunk, self.pods_offset, pods_number) else {
+            return;
+        };
+
+        let double_click = self
+            .last_click
+            .is_some_and(|(at, clicked)| clicked == index && at.elapsed() < DOUBLE_CLICK_INTERVAL);
+        self.selected_pod_index = index;
+        if double_click {
+            self.last_click = None;
+            self.pass_to_app(InputAction::FetchLogs);
+        } else {
+            self.last_click = Some((Instant::now(), index));
+        }
+    }
+
+    /// Scrolls the same way the table does, keeping the selected pod just in view.
+    pub(super) fn follow_pods_scroll(&mut self, chunk_height: u16) {
+        // The table isn't drawn while the logs are maximised, so it keeps its offset
+        if self.logs_maximised {
+            return;
+        }
+        self.pods_offset = scroll_offset(
+            self.pods_offset,
+            self.selected_pod_index,
+            self.app.get_pods_number(),
+            chunk_height,
+        );
+    }
+}
+
+/// Pod shown on the row of the table, below the top border and the header row.
+fn pod_at_row(row: u16, chunk: Rect, offset: usize, pods_number: usize) -> Option<usize> {
+    let first_row = chunk.top() + 2;
+    if row < first_row || row + 1 >= chunk.bottom() {
+        return None;
+    }
+    let index = offset + usize::from(row - first_row);
+    (index < pods_number).then_some(index)
+}
+
+/// First pod shown by a table of the height once the selected pod is scrolled into view.
+fn scroll_offset(offset: usize, selected: usize, pods_number: usize, chunk_height: u16) -> usize {
+    let visible_rows = table_page_size(chunk_height);
+    let offset = offset.min(pods_number.saturating_sub(1));
+    if selected < offset {
+        selected
+    } else if selected >= offset + visible_rows {
+        selected + 1 - visible_rows
+    } else {
+        offset
+    }
+}
+
+impl UiContext {
+    fn pane_at(&self, column: u16, row: u16) -> Option<Pane> {
+        [
+            (Pane::PodList, self.pods_chunk),
+            (Pane::Details, self.details_chunk),
+            (Pane::Logs, self.logs_chunk),
+        ]
+        .into_iter()
+        .find(|(_, chunk)| {
+            (chunk.left()..chunk.right()).contains(&column)
+                && (chunk.top()..chunk.bottom()).contains(&row)
+        })
+        .map(|(pane, _)| pane)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> UiContext {
+        UiContext {
+            pods_chunk: Rect::new(0, 1, 40, 10),
+            details_chunk: Rect::new(40, 1, 40, 4),
+            logs_chunk: Rect::new(40, 5, 40, 6),
+            manifest_chunk_height: 0,
+        }
+    }
+
+    #[test]
+    fn find_the_pane_under_the_cursor() {
+        // given
+        let context = context();
+
+        // when
+        let panes = [
+            context.pane_at(0, 1),
+            context.pane_at(39, 10),
+            context.pane_at(40, 4),
+            context.pane_at(79, 5),
+            context.pane_at(10, 0),
+            context.pane_at(80, 5),
+        ];
+
+        // then
+        assert_eq!(
+            panes,
+            [
+                Some(Pane::PodList),
+                Some(Pane::PodList),
+                Some(Pane::Details),
+                Some(Pane::Logs),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn map_the_clicked_row_to_a_pod() {
+        // given
+        let chunk = context().pods_chunk;
+
+        // when
+        let first = pod_at_row(3, chunk, 0, 20);
+        let scrolled = pod_at_row(5, chunk, 12, 20);
+        let header = pod_at_row(2, chunk, 0, 20);
+        let bottom_border = pod_at_row(10, chunk, 0, 20);
+        let past_the_pods = pod_at_row(9, chunk, 15, 20);
+
+        // then
+        assert_eq!(first, Some(0));
+        assert_eq!(scrolled, Some(14));
+        assert_eq!(header, None);
+        assert_eq!(bottom_border, None);
+        assert_eq!(past_the_pods, None);
+    }
+
+    #[test]
+    fn scroll_the_selected_pod_into_view() {
+        // given 7 rows between the borders and the header
+        let height = 10;
+
+        // when
+        let below = scroll_offset(0, 9, 20, height);
+        let above = scroll_offset(8, 3, 20, height);
+        let visible = scroll_offset(5, 8, 20, height);
+        let shrunk_list = scroll_offset(15, 2, 3, height);
+
+        // then
+        assert_eq!(below, 3);
+        assert_eq!(above, 3);
+        assert_eq!(visible, 5);
+        assert_eq!(shrunk_list, 2);
+    }
+}
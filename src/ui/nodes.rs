@@ -0,0 +1,90 @@
+use crate::node::Node;
+
+use super::{theme::Theme, widgets::header_cell};
+
+use tui::{
+    layout::Constraint,
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Cell, List, ListItem, Row, Table},
+};
+
+pub fn render_nodes_list<'a>(nodes: &'a [Node], theme: &Theme) -> List<'a> {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(theme.border())
+        .title("Nodes")
+        .border_type(BorderType::Plain);
+
+    let items: Vec<_> = nodes
+        .iter()
+        .map(|node| {
+            ListItem::new(Spans::from(vec![Span::styled(
+                node.name.as_str(),
+                node_style(node, theme),
+            )]))
+        })
+        .collect();
+
+    List::new(items)
+        .block(block)
+        .highlight_style(theme.highlight())
+}
+
+pub fn render_node_details<'a>(selected: Option<&'a Node>, theme: &Theme) -> Table<'a> {
+    let rows = match selected {
+        Some(node) => vec![Row::new(vec![
+            Cell::from(Span::raw(node.status.as_str())),
+            Cell::from(Span::raw(node.roles.as_str())),
+            Cell::from(Span::raw(node.version.as_str())),
+            Cell::from(Span::raw(node.cpu.as_str())),
+            Cell::from(Span::raw(node.memory.as_str())),
+            Cell::from(Span::raw(node.pods.to_string())),
+            Cell::from(Span::raw(match node.taints.is_empty() {
+                true => "<none>".to_string(),
+                false => node.taints.join(", "),
+            })),
+        ])],
+        None => vec![],
+    };
+
+    let header_row = Row::new(vec![
+        header_cell("Status"),
+        header_cell("Roles"),
+        header_cell("Version"),
+        header_cell("CPU"),
+        header_cell("Memory"),
+        header_cell("Pods"),
+        header_cell("Taints"),
+    ]);
+
+    Table::new(rows)
+        .header(header_row)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .style(theme.border())
+                .title("Detail")
+                .border_type(BorderType::Plain),
+        )
+        .widths(&[
+            Constraint::Percentage(20),
+            Constraint::Percentage(12),
+            Constraint::Percentage(10),
+            Constraint::Percentage(8),
+            Constraint::Percentage(10),
+            Constraint::Percentage(5),
+            Constraint::Percentage(35),
+        ])
+}
+
+/// Nodes which are not ready or don't accept new pods stand out.
+fn node_style(node: &Node, theme: &Theme) -> Style {
+    if !node.status.split(',').any(|it| it == "Ready") {
+        theme.fg(theme.error)
+    } else if node.status.contains("SchedulingDisabled") {
+        theme.fg(theme.warning)
+    } else {
+        Style::default()
+    }
+}
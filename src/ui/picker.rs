@@ -0,0 +1,113 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    text::Spans,
+    widgets::{Block, BorderType, Borders, List, ListItem, ListState},
+};
+
+use super::theme::Theme;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PickerKind {
+    ResourceKind,
+    PodColumn,
+}
+
+impl PickerKind {
+    fn title(&self) -> &'static str {
+        match self {
+            PickerKind::ResourceKind => "Resource kind",
+            PickerKind::PodColumn => "Show/hide column",
+        }
+    }
+}
+
+pub enum PickerResult {
+    Pending,
+    Cancelled,
+    Picked(String),
+}
+
+/// List of options narrowed down by typing, rendered over the body of the screen.
+pub struct Picker {
+    kind: PickerKind,
+    options: Vec<String>,
+    filter: String,
+    selected: usize,
+    list_state: ListState,
+}
+
+impl Picker {
+    pub fn new(kind: PickerKind, options: Vec<String>) -> Picker {
+        Picker {
+            kind,
+            options,
+            filter: String::new(),
+            selected: 0,
+            list_state: ListState::default(),
+        }
+    }
+
+    pub fn kind(&self) -> PickerKind {
+        self.kind
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PickerResult {
+        match key.code {
+            KeyCode::Esc => return PickerResult::Cancelled,
+            KeyCode::Enter => {
+                return match self.filtered_options().get(self.selected) {
+                    Some(option) => PickerResult::Picked(option.to_string()),
+                    None => PickerResult::Pending,
+                }
+            }
+            KeyCode::Down if self.selected + 1 < self.filtered_options().len() => {
+                self.selected += 1
+            }
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.selected = 0;
+            }
+            KeyCode::Char(c) => {
+                self.filter.push(c);
+                self.selected = 0;
+            }
+            _ => {}
+        }
+        PickerResult::Pending
+    }
+
+    pub fn render(&mut self, theme: &Theme) -> (List<'static>, &mut ListState) {
+        let items: Vec<_> = self
+            .filtered_options()
+            .into_iter()
+            .map(|it| ListItem::new(Spans::from(it.to_owned())))
+            .collect();
+        self.list_state.select(Some(self.selected));
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .style(theme.overlay_border())
+                    .title(format!(
+                        "{}: {}_ (Enter - pick, Esc - cancel)",
+                        self.kind.title(),
+                        self.filter
+                    ))
+                    .border_type(BorderType::Double),
+            )
+            .style(theme.text())
+            .highlight_style(theme.highlight());
+        (list, &mut self.list_state)
+    }
+
+    fn filtered_options(&self) -> Vec<&str> {
+        let filter = self.filter.to_lowercase();
+        self.options
+            .iter()
+            .filter(|it| it.to_lowercase().contains(&filter))
+            .map(|it| it.as_str())
+            .collect()
+    }
+}
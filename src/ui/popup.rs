@@ -0,0 +1,136 @@
+use std::path::PathBuf;
+
+use crate::node::NodeOperation;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+};
+
+use super::theme::Theme;
+
+/// Action waiting for the user's confirmation.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Confirmation {
+    ApplyManifest(PathBuf),
+    RestartDeployment(String),
+    OperateOnNode(NodeOperation, String),
+    /// Kind and name of the resource.
+    DeleteResource(String, String),
+}
+
+enum PopupKind {
+    Info,
+    Error,
+    Confirm(Confirmation),
+}
+
+pub enum PopupResult {
+    Pending,
+    Closed,
+    Confirmed(Confirmation),
+    Rejected(Confirmation),
+}
+
+/// Scrollable message rendered over the body of the screen.
+pub struct Popup {
+    title: String,
+    lines: Vec<String>,
+    kind: PopupKind,
+    scroll: u16,
+}
+
+impl Popup {
+    pub fn info(title: &str, message: &str) -> Popup {
+        Popup::new(title, message, PopupKind::Info)
+    }
+
+    pub fn error(title: &str, message: &str) -> Popup {
+        Popup::new(title, message, PopupKind::Error)
+    }
+
+    pub fn confirm(title: &str, message: &str, confirmation: Confirmation) -> Popup {
+        Popup::new(title, message, PopupKind::Confirm(confirmation))
+    }
+
+    pub fn is_confirmation(&self) -> bool {
+        matches!(self.kind, PopupKind::Confirm(_))
+    }
+
+    fn new(title: &str, message: &str, kind: PopupKind) -> Popup {
+        Popup {
+            title: title.to_owned(),
+            lines: message.lines().map(|it| it.to_owned()).collect(),
+            kind,
+            scroll: 0,
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PopupResult {
+        match key.code {
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            code => return self.answer(code),
+        }
+        PopupResult::Pending
+    }
+
+    fn answer(&self, code: KeyCode) -> PopupResult {
+        match (&self.kind, code) {
+            (PopupKind::Confirm(confirmation), KeyCode::Char('y')) => {
+                PopupResult::Confirmed(confirmation.clone())
+            }
+            (PopupKind::Confirm(confirmation), KeyCode::Char('n') | KeyCode::Esc) => {
+                PopupResult::Rejected(confirmation.clone())
+            }
+            (PopupKind::Info | PopupKind::Error, KeyCode::Esc | KeyCode::Enter) => {
+                PopupResult::Closed
+            }
+            _ => PopupResult::Pending,
+        }
+    }
+
+    pub fn render(&self, theme: &Theme) -> Paragraph<'_> {
+        let (border_color, hint) = match self.kind {
+            PopupKind::Info => (theme.border, "Enter/Esc - close"),
+            PopupKind::Error => (theme.error, "Enter/Esc - close"),
+            PopupKind::Confirm(_) => (theme.accent, "y - confirm, n/Esc - cancel"),
+        };
+
+        let text: Vec<_> = self
+            .lines
+            .iter()
+            .map(|it| Spans::from(Span::styled(it.as_str(), self.line_style(it, theme))))
+            .collect();
+
+        Paragraph::new(text)
+            .wrap(Wrap { trim: false })
+            .scroll((self.scroll, 0))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .style(theme.fg(border_color))
+                    .title(format!("{} ({hint})", self.title))
+                    .border_type(BorderType::Double),
+            )
+    }
+
+    fn line_style(&self, line: &str, theme: &Theme) -> Style {
+        let style = theme.text();
+        match self.kind {
+            // Confirmations show diffs of the pending changes
+            PopupKind::Confirm(_) => match line {
+                l if l.starts_with("+++") || l.starts_with("---") => style.fg(theme.secondary),
+                l if l.starts_with('+') => style.fg(theme.good),
+                l if l.starts_with('-') => style.fg(theme.error),
+                l if l.starts_with("@@") => style.fg(theme.key),
+                _ => style,
+            },
+            _ => style,
+        }
+    }
+}
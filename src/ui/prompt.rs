@@ -0,0 +1,161 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use tui::{
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Paragraph},
+};
+
+use super::theme::Theme;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PromptKind {
+    ManifestSearch,
+    ScaleDeployment,
+    UndoRollout,
+    PodFilter,
+    LabelSelector,
+    FieldSelector,
+    NewWorkspace,
+    Command,
+}
+
+impl PromptKind {
+    fn title(&self) -> &'static str {
+        match self {
+            PromptKind::ManifestSearch => "Search",
+            PromptKind::ScaleDeployment => "Number of replicas",
+            PromptKind::UndoRollout => "Undo to revision (empty - previous one)",
+            PromptKind::PodFilter => "Filter pods by name or status",
+            PromptKind::LabelSelector => "Label selector, ex: app=api,tier!=cache (empty - none)",
+            PromptKind::FieldSelector => "Field selector, ex: status.phase=Running (empty - none)",
+            PromptKind::Command => "Command, ex: ns kube-system, logs --since 10m (Tab - complete)",
+            PromptKind::NewWorkspace => {
+                "Namespace and context, ex: kube-system prod (no context - same one)"
+            }
+        }
+    }
+
+    fn prefix(&self) -> &'static str {
+        match self {
+            PromptKind::ManifestSearch => "/",
+            PromptKind::Command => ":",
+            _ => "",
+        }
+    }
+}
+
+pub enum PromptResult {
+    Pending,
+    Cancelled,
+    Submitted(String),
+}
+
+/// Single line text input, rendered in place of the footer while it is active.
+pub struct Prompt {
+    kind: PromptKind,
+    input: String,
+    /// Previous inputs, browsed with the up and down keys.
+    history: Vec<String>,
+    history_index: Option<usize>,
+    /// Shown after the input until the next key.
+    error: Option<String>,
+}
+
+impl Prompt {
+    pub fn new(kind: PromptKind) -> Prompt {
+        Prompt::with_input(kind, "")
+    }
+
+    /// Prompt starting with a previous value, which can be edited further.
+    pub fn with_input(kind: PromptKind, input: &str) -> Prompt {
+        Prompt {
+            kind,
+            input: input.to_owned(),
+            history: vec![],
+            history_index: None,
+            error: None,
+        }
+    }
+
+    pub fn with_history(kind: PromptKind, history: &[String]) -> Prompt {
+        Prompt {
+            history: history.to_vec(),
+            ..Prompt::new(kind)
+        }
+    }
+
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    pub fn set_input(&mut self, input: String) {
+        self.input = input;
+        self.error = None;
+    }
+
+    pub fn kind(&self) -> PromptKind {
+        self.kind
+    }
+
+    pub fn set_error(&mut self, error: String) {
+        self.error = Some(error);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PromptResult {
+        self.error = None;
+        match key.code {
+            KeyCode::Char(c) => {
+                self.input.push(c);
+                PromptResult::Pending
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                PromptResult::Pending
+            }
+            KeyCode::Up if !self.history.is_empty() => {
+                let index = match self.history_index {
+                    Some(index) => index.saturating_sub(1),
+                    None => self.history.len() - 1,
+                };
+                self.history_index = Some(index);
+                self.input = self.history[index].clone();
+                PromptResult::Pending
+            }
+            KeyCode::Down => {
+                match self.history_index {
+                    Some(index) if index + 1 < self.history.len() => {
+                        self.history_index = Some(index + 1);
+                        self.input = self.history[index + 1].clone();
+                    }
+                    Some(_) => {
+                        self.history_index = None;
+                        self.input.clear();
+                    }
+                    None => {}
+                }
+                PromptResult::Pending
+            }
+            KeyCode::Enter => PromptResult::Submitted(self.input.clone()),
+            KeyCode::Esc => PromptResult::Cancelled,
+            _ => PromptResult::Pending,
+        }
+    }
+
+    pub fn render(&self, theme: &Theme) -> Paragraph<'static> {
+        let mut spans = vec![Span::raw(format!("{}{}_", self.kind.prefix(), self.input))];
+        if let Some(error) = &self.error {
+            spans.push(Span::styled(format!("  {error}"), theme.fg(theme.error)));
+        }
+        Paragraph::new(Spans::from(spans))
+            .style(theme.text())
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .style(theme.overlay_border())
+                    .title(format!(
+                        "{} (Enter - confirm, Esc - cancel)",
+                        self.kind.title()
+                    ))
+                    .border_type(BorderType::Plain),
+            )
+    }
+}
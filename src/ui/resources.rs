@@ -0,0 +1,69 @@
+use crate::resource_table::ResourceTable;
+
+use super::{theme::Theme, widgets::header_cell};
+
+use tui::{
+    layout::Constraint,
+    style::Style,
+    text::{Span, Spans},
+    widgets::{Block, BorderType, Borders, Cell, List, ListItem, Row, Table},
+};
+
+pub fn render_resources_list<'a>(resources: Option<&'a ResourceTable>, theme: &Theme) -> List<'a> {
+    let title = match resources {
+        Some(resources) => format!("Resources: {}", resources.kind()),
+        None => "Resources".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(theme.border())
+        .title(title)
+        .border_type(BorderType::Plain);
+
+    let items: Vec<_> = match resources {
+        Some(resources) => (0..resources.len())
+            .flat_map(|index| resources.name(index))
+            .map(|name| ListItem::new(Spans::from(vec![Span::styled(name, Style::default())])))
+            .collect(),
+        None => vec![ListItem::new("Press 'k' to pick a kind.")],
+    };
+
+    List::new(items)
+        .block(block)
+        .highlight_style(theme.highlight())
+}
+
+/// All printer columns of the selected resource, with equal widths as their number varies.
+pub fn render_resource_details<'a>(
+    resources: Option<&'a ResourceTable>,
+    selected_index: usize,
+    theme: &Theme,
+) -> (Table<'a>, Vec<Constraint>) {
+    let (headers, row) = match resources {
+        Some(resources) => (
+            resources.headers().as_slice(),
+            resources.rows().get(selected_index),
+        ),
+        None => (&[] as &[String], None),
+    };
+
+    let rows = match row {
+        Some(row) => vec![Row::new(
+            row.iter()
+                .map(|it| Cell::from(Span::raw(it.as_str())))
+                .collect::<Vec<_>>(),
+        )],
+        None => vec![],
+    };
+    let header_row = Row::new(headers.iter().map(header_cell).collect::<Vec<_>>());
+    let widths = vec![Constraint::Ratio(1, headers.len().max(1) as u32); headers.len()];
+
+    let details = Table::new(rows).header(header_row).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .style(theme.border())
+            .title("Detail")
+            .border_type(BorderType::Plain),
+    );
+    (details, widths)
+}
@@ -0,0 +1,340 @@
+use std::{collections::HashMap, env, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+use tui::style::{Color, Modifier, Style};
+
+use crate::errors::Error;
+
+/// Colours of the screen by their role, so the renderers never name a colour themselves.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Theme {
+    pub text: Color,
+    pub border: Color,
+    /// Active tab, overlays and the background of the selected rows.
+    pub accent: Color,
+    /// Text of the selected rows, on top of the accent.
+    pub selected_text: Color,
+    pub info: Color,
+    pub muted: Color,
+    pub secondary: Color,
+    pub good: Color,
+    pub warning: Color,
+    pub error: Color,
+    /// Characters matched by the filters.
+    pub matched: Color,
+    pub key: Color,
+    pub literal: Color,
+    /// Background of the current line and of the search matches in the manifest.
+    pub selection: Color,
+    pub search: Color,
+}
+
+/// A custom theme from the config, ex:
+///
+/// ```toml
+/// [themes.solarized]
+/// base = "dark"
+/// accent = "#b58900"
+/// text = "gray"
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ThemeFile {
+    /// Built-in theme providing the colours which aren't set, `dark` by default.
+    pub base: Option<String>,
+    #[serde(flatten)]
+    pub colors: HashMap<String, String>,
+}
+
+impl Theme {
+    pub fn dark() -> Theme {
+        Theme {
+            text: Color::White,
+            border: Color::White,
+            accent: Color::Yellow,
+            selected_text: Color::Black,
+            info: Color::LightCyan,
+            muted: Color::DarkGray,
+            secondary: Color::Gray,
+            good: Color::Green,
+            warning: Color::LightYellow,
+            error: Color::Red,
+            matched: Color::LightRed,
+            key: Color::Cyan,
+            literal: Color::Magenta,
+            selection: Color::DarkGray,
+            search: Color::Blue,
+        }
+    }
+
+    pub fn light() -> Theme {
+        Theme {
+            text: Color::Black,
+            border: Color::DarkGray,
+            accent: Color::Blue,
+            selected_text: Color::White,
+            info: Color::Blue,
+            muted: Color::Gray,
+            secondary: Color::DarkGray,
+            good: Color::Green,
+            warning: Color::Rgb(175, 95, 0),
+            error: Color::Red,
+            matched: Color::Magenta,
+            key: Color::Blue,
+            literal: Color::Magenta,
+            selection: Color::Rgb(215, 215, 215),
+            search: Color::LightCyan,
+        }
+    }
+
+    pub fn high_contrast() -> Theme {
+        Theme {
+            text: Color::White,
+            border: Color::White,
+            accent: Color::LightYellow,
+            selected_text: Color::Black,
+            info: Color::LightCyan,
+            muted: Color::Gray,
+            secondary: Color::White,
+            good: Color::LightGreen,
+            warning: Color::LightYellow,
+            error: Color::LightRed,
+            matched: Color::LightMagenta,
+            key: Color::LightCyan,
+            literal: Color::LightMagenta,
+            selection: Color::Blue,
+            search: Color::Magenta,
+        }
+    }
+
+    /// Terminal's own colours only, the selections are shown with reversed video.
+    pub fn monochrome() -> Theme {
+        Theme {
+            text: Color::Reset,
+            border: Color::Reset,
+            accent: Color::Reset,
+            selected_text: Color::Reset,
+            info: Color::Reset,
+            muted: Color::Reset,
+            secondary: Color::Reset,
+            good: Color::Reset,
+            warning: Color::Reset,
+            error: Color::Reset,
+            matched: Color::Reset,
+            key: Color::Reset,
+            literal: Color::Reset,
+            selection: Color::Reset,
+            search: Color::Reset,
+        }
+    }
+
+    /// Finds the theme by its name, the custom ones may override the built-in ones.
+    /// `NO_COLOR` always wins, see https://no-color.org.
+    pub fn load(name: &str, themes: &HashMap<String, ThemeFile>) -> Result<Theme, Error> {
+        if env::var_os("NO_COLOR").is_some_and(|it| !it.is_empty()) {
+            return Ok(Theme::monochrome());
+        }
+        Theme::named(name, themes)
+    }
+
+    fn named(name: &str, themes: &HashMap<String, ThemeFile>) -> Result<Theme, Error> {
+        if let Some(file) = themes.get(name) {
+            let base = match &file.base {
+                Some(base) => Theme::built_in(base)?,
+                None => Theme::dark(),
+            };
+            return Theme::from_file(base, file);
+        }
+        Theme::built_in(name)
+    }
+
+    fn built_in(name: &str) -> Result<Theme, Error> {
+        match name {
+            "dark" => Ok(Theme::dark()),
+            "light" => Ok(Theme::light()),
+            "high-contrast" => Ok(Theme::high_contrast()),
+            "monochrome" => Ok(Theme::monochrome()),
+            _ => Err(Error::InvalidTheme(format!("unknown theme {name}"))),
+        }
+    }
+
+    fn from_file(mut theme: Theme, file: &ThemeFile) -> Result<Theme, Error> {
+        for (role, color) in &file.colors {
+            let color = parse_color(color)?;
+            let slot = match role.as_str() {
+                "text" => &mut theme.text,
+                "border" => &mut theme.border,
+                "accent" => &mut theme.accent,
+                "selected_text" => &mut theme.selected_text,
+                "info" => &mut theme.info,
+                "muted" => &mut theme.muted,
+                "secondary" => &mut theme.secondary,
+                "good" => &mut theme.good,
+                "warning" => &mut theme.warning,
+                "error" => &mut theme.error,
+                "matched" => &mut theme.matched,
+                "key" => &mut theme.key,
+                "literal" => &mut theme.literal,
+                "selection" => &mut theme.selection,
+                "search" => &mut theme.search,
+                _ => return Err(Error::InvalidTheme(format!("unknown colour {role}"))),
+            };
+            *slot = color;
+        }
+        Ok(theme)
+    }
+
+    pub fn fg(&self, color: Color) -> Style {
+        Style::default().fg(color)
+    }
+
+    pub fn text(&self) -> Style {
+        self.fg(self.text)
+    }
+
+    pub fn border(&self) -> Style {
+        self.fg(self.border)
+    }
+
+    /// Borders of the popups, prompts and pickers shown over the views.
+    pub fn overlay_border(&self) -> Style {
+        self.fg(self.accent)
+    }
+
+    /// Borders of the pane taking the arrow and page keys stand out.
+    pub fn pane_border(&self, focused: bool) -> Style {
+        match focused {
+            true => self.fg(self.accent).add_modifier(Modifier::BOLD),
+            false => self.border(),
+        }
+    }
+
+    pub fn title(&self) -> Style {
+        self.fg(self.accent).add_modifier(Modifier::BOLD)
+    }
+
+    pub fn highlight(&self) -> Style {
+        let style = Style::default().add_modifier(Modifier::BOLD);
+        match self.accent {
+            Color::Reset => style.add_modifier(Modifier::REVERSED),
+            accent => style.bg(accent).fg(self.selected_text),
+        }
+    }
+
+    pub fn selected_line(&self) -> Style {
+        match self.selection {
+            Color::Reset => Style::default().add_modifier(Modifier::REVERSED),
+            selection => Style::default().bg(selection),
+        }
+    }
+
+    pub fn search_match(&self) -> Style {
+        match self.search {
+            Color::Reset => Style::default().add_modifier(Modifier::UNDERLINED),
+            search => Style::default().bg(search),
+        }
+    }
+}
+
+/// Accepts the colour names (ex: `light-red`), `#rrggbb` and the 256 colour palette indexes.
+fn parse_color(color: &str) -> Result<Color, Error> {
+    let invalid = || Error::InvalidTheme(format!("invalid colour {color}"));
+    if let Some(hex) = color.strip_prefix('#') {
+        let channel = |range| {
+            hex.get(range)
+                .and_then(|it| u8::from_str_radix(it, 16).ok())
+                .ok_or_else(invalid)
+        };
+        return match hex.len() {
+            6 => Ok(Color::Rgb(channel(0..2)?, channel(2..4)?, channel(4..6)?)),
+            _ => Err(invalid()),
+        };
+    }
+    if let Ok(index) = u8::from_str(color) {
+        return Ok(Color::Indexed(index));
+    }
+
+    let color = match color.to_lowercase().replace('_', "-").as_str() {
+        "reset" | "default" => Color::Reset,
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "dark-gray" | "dark-grey" => Color::DarkGray,
+        "light-red" => Color::LightRed,
+        "light-green" => Color::LightGreen,
+        "light-yellow" => Color::LightYellow,
+        "light-blue" => Color::LightBlue,
+        "light-magenta" => Color::LightMagenta,
+        "light-cyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return Err(invalid()),
+    };
+    Ok(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tui::style::{Color, Modifier};
+
+    use super::{parse_color, Theme, ThemeFile};
+
+    #[test]
+    fn parse_colors() {
+        assert_eq!(parse_color("light-red").unwrap(), Color::LightRed);
+        assert_eq!(parse_color("Dark_Gray").unwrap(), Color::DarkGray);
+        assert_eq!(parse_color("#b58900").unwrap(), Color::Rgb(181, 137, 0));
+        assert_eq!(parse_color("208").unwrap(), Color::Indexed(208));
+        parse_color("#b589").unwrap_err();
+        parse_color("purple").unwrap_err();
+    }
+
+    #[test]
+    fn override_colors_of_the_base_theme() {
+        //given
+        let file = ThemeFile {
+            base: Some("light".to_owned()),
+            colors: HashMap::from([("accent".to_owned(), "#b58900".to_owned())]),
+        };
+        let themes = HashMap::from([("solarized".to_owned(), file)]);
+
+        // when
+        let theme = Theme::named("solarized", &themes).unwrap();
+
+        //then
+        assert_eq!(theme.accent, Color::Rgb(181, 137, 0));
+        assert_eq!(theme.text, Theme::light().text);
+    }
+
+    #[test]
+    fn reject_unknown_themes_and_colors() {
+        let file = ThemeFile {
+            base: None,
+            colors: HashMap::from([("background".to_owned(), "red".to_owned())]),
+        };
+        let themes = HashMap::from([("mine".to_owned(), file)]);
+
+        Theme::named("solarized", &themes).unwrap_err();
+        Theme::named("mine", &themes).unwrap_err();
+    }
+
+    #[test]
+    fn highlight_the_border_of_the_focused_pane() {
+        // given
+        let theme = Theme::dark();
+
+        // when
+        let focused = theme.pane_border(true);
+        let unfocused = theme.pane_border(false);
+
+        // then
+        assert_eq!(focused.fg, Some(theme.accent));
+        assert!(focused.add_modifier.contains(Modifier::BOLD));
+        assert_eq!(unfocused, theme.border());
+    }
+}
@@ -0,0 +1,45 @@
+use std::borrow::Cow;
+
+use tui::{
+    style::{Modifier, Style},
+    text::Span,
+    widgets::Cell,
+};
+
+/// Bold cell of a table header row.
+pub fn header_cell<'a>(title: impl Into<Cow<'a, str>>) -> Cell<'a> {
+    Cell::from(Span::styled(
+        title,
+        Style::default().add_modifier(Modifier::BOLD),
+    ))
+}
+
+/// Lines a page up or down scrolls by in a bordered pane.
+pub fn page_size(chunk_height: u16) -> usize {
+    // Without the block borders
+    (chunk_height as usize).saturating_sub(2).max(1)
+}
+
+/// Rows a bordered table shows below its header row, which a page up or down scrolls by.
+pub fn table_page_size(chunk_height: u16) -> usize {
+    page_size(chunk_height).saturating_sub(1).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scroll_a_page_without_the_borders() {
+        assert_eq!(page_size(12), 10);
+        assert_eq!(page_size(2), 1);
+        assert_eq!(page_size(0), 1);
+    }
+
+    #[test]
+    fn scroll_a_table_page_without_the_header() {
+        assert_eq!(table_page_size(12), 9);
+        assert_eq!(table_page_size(3), 1);
+        assert_eq!(table_page_size(0), 1);
+    }
+}
@@ -0,0 +1,289 @@
+use std::{mem, time::Instant};
+
+use tui::widgets::{ListState, TableState};
+
+use crate::app::{App, JobRequest};
+
+use super::{
+    help::Help, logs::LoggerWidget, manifest::ManifestWidget, picker::Picker, popup::Popup,
+    prompt::Prompt, Pane, View, UI,
+};
+
+/// App of a tab in the background, with the selections and widgets it was left with.
+pub struct Workspace {
+    pub app: App,
+    pub view: View,
+    pub previous_view: View,
+    pub pod_table_state: TableState,
+    pub selected_pod_index: usize,
+    pub pods_offset: usize,
+    pub deployment_list_state: ListState,
+    pub selected_deployment_index: usize,
+    pub resource_list_state: ListState,
+    pub selected_resource_index: usize,
+    pub events_table_state: TableState,
+    pub selected_event_index: usize,
+    pub events_refreshed_at: Instant,
+    pub metrics_refreshed_at: Option<Instant>,
+    pub node_list_state: ListState,
+    pub selected_node_index: usize,
+    pub logger_widget: LoggerWidget,
+    pub manifest_widget: ManifestWidget,
+    /// Left by a job which finished in the background, shown once the workspace is back.
+    pub popup: Option<Popup>,
+    pub status: Option<(String, Instant)>,
+    pub focus: Pane,
+    pub logs_maximised: bool,
+    /// Overlays left open, which act on the workspace they were opened in.
+    pub prompt: Option<Prompt>,
+    pub filter_before_prompt: Option<String>,
+    pub picker: Option<Picker>,
+    pub help: Option<Help>,
+}
+
+impl Workspace {
+    pub fn new(app: App, log_lines: usize) -> Workspace {
+        Workspace {
+            app,
+            view: View::Pods,
+            previous_view: View::Pods,
+            pod_table_state: TableState::default(),
+            selected_pod_index: 0,
+            pods_offset: 0,
+            deployment_list_state: ListState::default(),
+            selected_deployment_index: 0,
+            resource_list_state: ListState::default(),
+            selected_resource_index: 0,
+            events_table_state: TableState::default(),
+            selected_event_index: 0,
+            events_refreshed_at: Instant::now(),
+            metrics_refreshed_at: None,
+            node_list_state: ListState::default(),
+            selected_node_index: 0,
+            logger_widget: LoggerWidget::new(None, log_lines),
+            manifest_widget: ManifestWidget::new(),
+            popup: None,
+            status: None,
+            focus: Pane::PodList,
+            logs_maximised: false,
+            prompt: None,
+            filter_before_prompt: None,
+            picker: None,
+            help: None,
+        }
+    }
+}
+
+impl UI<'_> {
+    /// Titles of all the workspaces, in the order of the tab bar.
+    pub(super) fn workspace_titles(&self) -> Vec<String> {
+        let mut titles: Vec<String> = self.workspaces.iter().map(|it| title(&it.app)).collect();
+        titles.insert(self.active_workspace, title(self.app));
+        titles
+    }
+
+    /// Input is the namespace, then the context, which defaults to the one of the active workspace.
+    pub(super) fn open_workspace(&mut self, input: &str) {
+        let mut words = input.split_whitespace();
+        let Some(namespace) = words.next() else {
+            return;
+        };
+        let context = words.next().or(self.app.context()).map(|it| it.to_owned());
+
+        let app = self.workspace_app(namespace.to_owned(), context);
+        self.workspaces
+            .push(Workspace::new(app, self.config.log_lines));
+        self.switch_workspace(self.workspaces.len());
+        self.start_job(JobRequest::Pods);
+    }
+
+    /// Replaces the active workspace with a fresh one, keeping its place in the tab bar.
+    pub(super) fn replace_workspace(&mut self, namespace: String, context: Option<String>) {
+        let app = self.workspace_app(namespace, context);
+        self.swap_workspace(&mut Workspace::new(app, self.config.log_lines));
+        self.start_job(JobRequest::Pods);
+    }
+
+    /// Pods of the workspace are narrowed by the selectors configured for its context.
+    fn workspace_app(&self, namespace: String, context: Option<String>) -> App {
+        let config = self.config.for_context(context.as_deref());
+        App::new(namespace, context, config.selector, config.field_selector)
+    }
+
+    /// Index is the position in the tab bar.
+    pub(super) fn switch_workspace(&mut self, index: usize) {
+        if index == self.active_workspace || index > self.workspaces.len() {
+            return;
+        }
+
+        let mut workspace = self
+            .workspaces
+            .remove(background_index(index, self.active_workspace));
+        self.swap_workspace(&mut workspace);
+        self.workspaces
+            .insert(background_index(self.active_workspace, index), workspace);
+        self.active_workspace = index;
+    }
+
+    /// The next workspace takes the place of the closed one, the last one can't be closed.
+    pub(super) fn close_workspace(&mut self) {
+        if self.workspaces.is_empty() {
+            return;
+        }
+
+        let index = self.active_workspace.min(self.workspaces.len() - 1);
+        let mut workspace = self.workspaces.remove(index);
+        self.swap_workspace(&mut workspace);
+        self.active_workspace = index;
+    }
+
+    /// Puts the workspace in the foreground, and the active one in its place.
+    fn swap_workspace(&mut self, workspace: &mut Workspace) {
+        if let Some(popup) = workspace.popup.take() {
+            self.popup = Some(popup);
+        }
+        mem::swap(self.app, &mut workspace.app);
+        mem::swap(&mut self.view, &mut workspace.view);
+        mem::swap(&mut self.previous_view, &mut workspace.previous_view);
+        mem::swap(&mut self.pod_table_state, &mut workspace.pod_table_state);
+        mem::swap(
+            &mut self.selected_pod_index,
+            &mut workspace.selected_pod_index,
+        );
+        mem::swap(&mut self.pods_offset, &mut workspace.pods_offset);
+        mem::swap(
+            &mut self.deployment_list_state,
+            &mut workspace.deployment_list_state,
+        );
+        mem::swap(
+            &mut self.selected_deployment_index,
+            &mut workspace.selected_deployment_index,
+        );
+        mem::swap(
+            &mut self.resource_list_state,
+            &mut workspace.resource_list_state,
+        );
+        mem::swap(
+            &mut self.selected_resource_index,
+            &mut workspace.selected_resource_index,
+        );
+        mem::swap(
+            &mut self.events_table_state,
+            &mut workspace.events_table_state,
+        );
+        mem::swap(
+            &mut self.selected_event_index,
+            &mut workspace.selected_event_index,
+        );
+        mem::swap(
+            &mut self.events_refreshed_at,
+            &mut workspace.events_refreshed_at,
+        );
+        mem::swap(
+            &mut self.metrics_refreshed_at,
+            &mut workspace.metrics_refreshed_at,
+        );
+        mem::swap(&mut self.node_list_state, &mut workspace.node_list_state);
+        mem::swap(
+            &mut self.selected_node_index,
+            &mut workspace.selected_node_index,
+        );
+        mem::swap(&mut self.logger_widget, &mut workspace.logger_widget);
+        mem::swap(&mut self.manifest_widget, &mut workspace.manifest_widget);
+        mem::swap(&mut self.status, &mut workspace.status);
+        mem::swap(&mut self.focus, &mut workspace.focus);
+        mem::swap(&mut self.logs_maximised, &mut workspace.logs_maximised);
+        mem::swap(&mut self.prompt, &mut workspace.prompt);
+        mem::swap(
+            &mut self.filter_before_prompt,
+            &mut workspace.filter_before_prompt,
+        );
+        mem::swap(&mut self.picker, &mut workspace.picker);
+        mem::swap(&mut self.help, &mut workspace.help);
+        self.last_click = None;
+    }
+}
+
+/// Title in the tab bar, ex: `kube-system` or `prod:kube-system`.
+pub fn title(app: &App) -> String {
+    match app.context() {
+        Some(context) => format!("{context}:{}", app.namespace()),
+        None => app.namespace().to_owned(),
+    }
+}
+
+/// Index among the background workspaces of the one at `index` in the tab bar, the one at
+/// `active` being in the foreground.
+pub fn background_index(index: usize, active: usize) -> usize {
+    match index < active {
+        true => index,
+        false => index - 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        config::Config,
+        input::{event_loop::EventLoop, keymap::Keymap},
+        ui::{prompt::PromptKind, theme::Theme},
+    };
+
+    use super::*;
+
+    #[test]
+    fn index_background_workspaces_without_the_active_one() {
+        // given
+        let active = 1;
+
+        // when
+        let before = background_index(0, active);
+        let after = background_index(2, active);
+
+        // then
+        assert_eq!(before, 0);
+        assert_eq!(after, 1);
+    }
+
+    #[test]
+    fn keep_the_status_and_the_overlays_with_their_workspace() {
+        // given
+        let mut app = App::new("default".to_owned(), None, None, None);
+        let mut ui = UI::with_event_loop(
+            &mut app,
+            Config::default(),
+            Keymap::defaults(false),
+            Theme::dark(),
+            EventLoop::new(),
+        );
+        let other = App::new("kube-system".to_owned(), None, None, None);
+        ui.workspaces.push(Workspace::new(other, 50));
+        ui.status = Some(("Couldn't load pods: timeout".to_owned(), Instant::now()));
+        ui.prompt = Some(Prompt::new(PromptKind::LabelSelector));
+        ui.focus = Pane::Logs;
+        ui.logs_maximised = true;
+
+        // when
+        ui.switch_workspace(1);
+        let switched = (
+            ui.status.clone(),
+            ui.prompt.is_some(),
+            ui.focus,
+            ui.logs_maximised,
+        );
+        ui.switch_workspace(0);
+
+        // then
+        assert_eq!(switched, (None, false, Pane::PodList, false));
+        assert_eq!(
+            ui.status.map(|(message, _)| message).as_deref(),
+            Some("Couldn't load pods: timeout")
+        );
+        assert_eq!(
+            ui.prompt.map(|it| it.kind()),
+            Some(PromptKind::LabelSelector)
+        );
+        assert_eq!(ui.focus, Pane::Logs);
+        assert!(ui.logs_maximised);
+    }
+}